@@ -0,0 +1,113 @@
+use geo_types::{LineString, Polygon};
+
+use crate::{GeoTransformer, Window};
+
+/// Clip the ring to the unit pixel with the top-left corner at `cell` using the
+/// Sutherland–Hodgman algorithm.
+///
+/// The ring may be concave, as the pixel is convex the area of the result is still exact.
+fn clip_to_cell(ring: &[(f64, f64)], cell: (f64, f64)) -> Vec<(f64, f64)> {
+    // (axis, boundary, inside is above the boundary)
+    let edges = [(0, cell.0, true), (0, cell.0 + 1.0, false), (1, cell.1, true), (1, cell.1 + 1.0, false)];
+    let mut output = ring.to_vec();
+    for &(axis, boundary, above) in edges.iter() {
+        let input = std::mem::take(&mut output);
+        let value = |point: (f64, f64)| if axis == 0 { point.0 } else { point.1 };
+        let inside = |point: (f64, f64)| if above { value(point) >= boundary } else { value(point) <= boundary };
+        let crossing = |a: (f64, f64), b: (f64, f64)| {
+            let t = (boundary - value(a)) / (value(b) - value(a));
+            (a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1))
+        };
+
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+            match (inside(previous), inside(current)) {
+                (true, true) => output.push(current),
+                (true, false) => output.push(crossing(previous, current)),
+                (false, true) => {
+                    output.push(crossing(previous, current));
+                    output.push(current);
+                }
+                (false, false) => (),
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+/// Area of the ring using the shoelace formula.
+fn ring_area(ring: &[(f64, f64)]) -> f64 {
+    let twice_area: f64 = ring.iter().zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum();
+    twice_area.abs() / 2.0
+}
+
+impl GeoTransformer {
+    /// The fraction of the area of each pixel covered by the polygon.
+    ///
+    /// The polygon including its holes is converted to pixel space and clipped to every
+    /// pixel within its boundingbox, so only pixels of a dataset of the given size which are
+    /// touched by the polygon are yielded, with a fraction in `(0, 1]`. Pixels are yielded
+    /// row by row.
+    pub fn pixel_coverage(&self, polygon: &Polygon<f64>, size: (usize, usize)) -> impl Iterator<Item = ((usize, usize), f64)> {
+        let to_pixels = |ring: &LineString<f64>| -> Vec<(f64, f64)> {
+            ring.0.iter().map(|c| self.coordinate_to_fractional_pixel(*c)).collect()
+        };
+        let exterior = to_pixels(polygon.exterior());
+        let interiors: Vec<_> = polygon.interiors().iter().map(to_pixels).collect();
+
+        let (min, max) = exterior.iter().fold(
+            ((f64::INFINITY, f64::INFINITY), (f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |(min, max), p| ((min.0.min(p.0), min.1.min(p.1)), (max.0.max(p.0), max.1.max(p.1))),
+        );
+        let window = Window::from_pixel_bounds((min.0.floor(), min.1.floor()), (max.0.ceil(), max.1.ceil()), size);
+
+        let mut coverage = Vec::new();
+        if let Some(Window { offset, size }) = window {
+            for row in offset.1..(offset.1 + size.1) {
+                for col in offset.0..(offset.0 + size.0) {
+                    let cell = (col as f64, row as f64);
+                    let holes: f64 = interiors.iter().map(|ring| ring_area(&clip_to_cell(ring, cell))).sum();
+                    let fraction = (ring_area(&clip_to_cell(&exterior, cell)) - holes).clamp(0.0, 1.0);
+                    if fraction > 0.0 {
+                        coverage.push(((col, row), fraction));
+                    }
+                }
+            }
+        }
+        coverage.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use geo_types::{LineString, Polygon};
+
+    use crate::GeoTransformer;
+
+    #[test]
+    fn test_pixel_coverage() {
+        let geotransformer = GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+        let polygon = Polygon::new(
+            LineString::from(vec![(0.5, 0.0), (2.5, 0.0), (2.5, -2.0), (0.5, -2.0), (0.5, 0.0)]),
+            vec![LineString::from(vec![(1.0, -1.0), (2.0, -1.0), (2.0, -2.0), (1.0, -2.0), (1.0, -1.0)])],
+        );
+
+        let coverage: Vec<_> = geotransformer.pixel_coverage(&polygon, (10, 10)).collect();
+        assert_eq!(coverage.len(), 5);
+        let expected = [((0, 0), 0.5), ((1, 0), 1.0), ((2, 0), 0.5), ((0, 1), 0.5), ((2, 1), 0.5)];
+        for ((pixel, fraction), (expected_pixel, expected_fraction)) in coverage.iter().zip(expected.iter()) {
+            assert_eq!(pixel, expected_pixel);
+            assert_relative_eq!(*fraction, *expected_fraction, epsilon = 0.000001);
+        }
+
+        // clamped to the dataset
+        assert_eq!(geotransformer.pixel_coverage(&polygon, (1, 1)).count(), 1);
+    }
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use gdal::raster::Dataset;
+use geo_types::{Coordinate, Polygon, Rect};
+
+use crate::{GeoTransformer, GeoTransformError};
+
+/// A raster dataset opened together with its transformer, size and number of bands.
+pub struct RasterHandle {
+    dataset: Dataset,
+    geotransformer: GeoTransformer,
+    size: (usize, usize),
+    band_count: isize,
+}
+
+impl RasterHandle {
+    /// Open the dataset at `path` and read its geotransform.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<RasterHandle, GeoTransformError> {
+        let dataset = Dataset::open(path.as_ref())?;
+        let geotransformer = GeoTransformer::from_dataset(&dataset)?;
+        let size = dataset.size();
+        let band_count = dataset.count();
+        Ok(RasterHandle { dataset, geotransformer, size, band_count })
+    }
+
+    /// The opened dataset
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// The transformer of the geotransform of the dataset
+    pub fn geotransformer(&self) -> &GeoTransformer {
+        &self.geotransformer
+    }
+
+    /// Number of columns and rows of the dataset
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Number of raster bands of the dataset, band indexes start at 1
+    pub fn band_count(&self) -> isize {
+        self.band_count
+    }
+
+    /// The boundingbox of the dataset
+    pub fn bounds(&self) -> Rect<f64> {
+        self.geotransformer.bounds_from_size(self.size)
+    }
+
+    /// The outline of the dataset as a polygon
+    pub fn footprint(&self) -> Polygon<f64> {
+        self.geotransformer.footprint_polygon(self.size)
+    }
+
+    /// Sample the value of the pixel of the band containing the coordinate.
+    ///
+    /// Returns `None` when the coordinate is outside of the dataset or the pixel is nodata.
+    pub fn sample_nearest(&self, band_index: isize, coordinate: Coordinate<f64>) -> Result<Option<f64>, GeoTransformError> {
+        let band = self.dataset.rasterband(band_index)?;
+        Ok(self.geotransformer.sample_nearest(&band, coordinate)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::Coordinate;
+
+    use crate::{PixelAnchor, RasterHandle};
+
+    #[test]
+    fn test_raster_handle_bounds() {
+        let handle = RasterHandle::open("data/small.tiff").unwrap();
+        let bounds = handle.bounds();
+
+        // expected values where collected by using gdalinfo
+        assert_relative_eq!(bounds.min().x, 11.3610659, epsilon = 0.000001);
+        assert_relative_eq!(bounds.max().y, 46.2520256, epsilon = 0.000001);
+        assert_eq!(handle.footprint().exterior().num_coords(), 5);
+        assert_eq!(handle.size(), (53, 44));
+        assert_eq!(handle.band_count(), 15);
+    }
+
+    #[test]
+    fn test_raster_handle_sample_nearest() {
+        let handle = RasterHandle::open("data/small.tiff").unwrap();
+        let coordinate = handle.geotransformer().pixel_to_coordinate_anchored((10, 20), PixelAnchor::Center);
+
+        assert_eq!(handle.sample_nearest(1, coordinate).unwrap(), Some(8101.0));
+        assert_eq!(handle.sample_nearest(1, Coordinate { x: 0.0, y: 0.0 }).unwrap(), None);
+    }
+}
@@ -3,128 +3,2158 @@
 extern crate approx;
 
 use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::Arc;
 
+use gdal::metadata::Metadata;
+use gdal::raster::Dataset;
 use gdal::raster::dataset::GeoTransform;
-use geo_types::{Coordinate, Rect};
+use gdal::spatial_ref::{CoordTransform, SpatialRef};
+use geo_types::{Coordinate, LineString, Point, Polygon, Rect};
 
+pub use crate::handle::RasterHandle;
+pub use crate::sampling::PixelInterpretation;
+pub use crate::window::Window;
 
-fn rect_from_coordinates(c1: Coordinate<f64>, c2: Coordinate<f64>) -> Rect<f64> {
-    Rect::new(
-        Coordinate {
-            x: if c1.x > c2.x { c2.x } else { c1.x },
-            y: if c1.y > c2.y { c2.y } else { c1.y },
-        },
-        Coordinate {
-            x: if c1.x < c2.x { c2.x } else { c1.x },
-            y: if c1.y < c2.y { c2.y } else { c1.y },
-        },
-    )
+mod coverage;
+mod handle;
+mod sampling;
+mod window;
+
+
+/// The envelope of all coordinates. Must be given at least one coordinate.
+fn envelope_of_coordinates<I: IntoIterator<Item = Coordinate<f64>>>(coordinates: I) -> Rect<f64> {
+    let mut iter = coordinates.into_iter();
+    let first = iter.next().expect("envelope requires at least one coordinate");
+    let (min, max) = iter.fold((first, first), |(min, max), c| {
+        (
+            Coordinate { x: min.x.min(c.x), y: min.y.min(c.y) },
+            Coordinate { x: max.x.max(c.x), y: max.y.max(c.y) },
+        )
+    });
+    Rect::new(min, max)
+}
+
+/// The pixel positions along the outline of a dataset of the given size, starting
+/// at the top-left corner and going clockwise.
+///
+/// `points_per_edge` additional points are inserted between the corners of each edge. The
+/// ring is not closed.
+fn densified_outline_pixels(size: (usize, usize), points_per_edge: usize) -> Vec<(f64, f64)> {
+    let (width, height) = (size.0 as f64, size.1 as f64);
+    let corners = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)];
+    let segments = (points_per_edge + 1) as f64;
+
+    let mut pixels = Vec::with_capacity(4 * (points_per_edge + 1));
+    for (i, start) in corners.iter().enumerate() {
+        let end = corners[(i + 1) % corners.len()];
+        for step in 0..=points_per_edge {
+            let t = step as f64 / segments;
+            pixels.push((start.0 + (end.0 - start.0) * t, start.1 + (end.1 - start.1) * t));
+        }
+    }
+    pixels
+}
+
+/// Number of points GDAL inserts along each edge when transforming bounds
+/// between coordinate systems.
+const REPROJECTION_POINTS_PER_EDGE: usize = 21;
+
+/// Invert a geotransform, returns `None` when it is not invertible.
+fn invert_geotransform(gt: &GeoTransform) -> Option<GeoTransform> {
+    // ported from https://github.com/OSGeo/gdal/blob/master/gdal/gcore/gdaltransformer.cpp GDALInvGeoTransform
+
+    // special case - no rotation - to avoid computing the determinant
+    // and potential precision issues
+    if gt[2] == 0.0 && gt[4] == 0.0 && gt[1] != 0.0 && gt[5] != 0.0 {
+        return Some([
+            -gt[0] / gt[1],
+            1.0 / gt[1],
+            0.0,
+            -gt[3] / gt[5],
+            0.0,
+            1.0 / gt[5],
+        ]);
+    }
+
+    let det = gt[1] * gt[5] - gt[2] * gt[4];
+    let magnitude = gt[1].abs().max(gt[2].abs()).max(gt[4].abs().max(gt[5].abs()));
+    if det.abs() <= 1e-10 * magnitude * magnitude || !det.is_finite() {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+
+    Some([
+        (gt[2] * gt[3] - gt[0] * gt[5]) * inv_det,
+        gt[5] * inv_det,
+        -gt[2] * inv_det,
+        (-gt[1] * gt[3] + gt[0] * gt[4]) * inv_det,
+        -gt[4] * inv_det,
+        gt[1] * inv_det,
+    ])
+}
+
+/// Maximum relative difference of the pixel size terms of two geotransforms
+/// for them to be considered to be on grids of the same resolution.
+const PIXEL_SIZE_TOLERANCE: f64 = 1e-6;
+
+/// The location within a pixel a pixel coordinate refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PixelAnchor {
+    /// The top-left corner of the pixel, as used by GDAL
+    #[default]
+    Corner,
+    /// The center of the pixel
+    Center,
+}
+
+impl PixelAnchor {
+    /// Offset of the anchor from the top-left corner of the pixel in pixels
+    fn offset(self) -> f64 {
+        match self {
+            PixelAnchor::Corner => 0.0,
+            PixelAnchor::Center => 0.5,
+        }
+    }
+}
+
+/// Whether the far edges of a pixel belong to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IntervalMode {
+    /// Pixels cover `[col, col + 1)` and `[row, row + 1)`, as used by GDAL. A coordinate on
+    /// the right or bottom edge of a dataset is outside of it, and a rect touching a
+    /// pixel only with its edge does not cover it.
+    #[default]
+    HalfOpen,
+    /// Pixels cover `[col, col + 1]` and `[row, row + 1]`. A coordinate on the right or bottom
+    /// edge of a dataset belongs to its last column or row, and a rect touching a pixel
+    /// with its edge covers it.
+    Closed,
+}
+
+/// The conventions a `GeoTransformer` uses when converting between pixels and coordinates.
+///
+/// The geotransform itself always refers to the top-left corner of the top-left pixel, as
+/// GDAL reports it. The conventions only decide which coordinate represents a pixel and
+/// which pixels a coordinate or rect on a pixel boundary belongs to. Bounds and footprints
+/// always describe the whole area covered by the pixels. The default are the conventions
+/// of GDAL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Conventions {
+    /// The location within a pixel returned by `pixel_to_coordinate` and `pixel_to_point`
+    pub anchor: PixelAnchor,
+    /// Which pixel boundaries belong to a pixel in `coordinate_to_pixel_checked`,
+    /// `kernel_fits`, `rect_to_window` and `enclosing_window`
+    pub interval: IntervalMode,
+}
+
+impl Conventions {
+    /// The index of the pixel along an axis of `len` pixels containing the fractional
+    /// pixel position `value`.
+    ///
+    /// Only differs from flooring for a position on the far edge, which belongs to the
+    /// last pixel for closed intervals.
+    fn pixel_index(&self, value: f64, len: usize) -> f64 {
+        if self.interval == IntervalMode::Closed && len > 0 && value == len as f64 {
+            (len - 1) as f64
+        } else {
+            value.floor()
+        }
+    }
+
+    /// The index of the first pixel covered by a rect starting at the fractional pixel
+    /// position `value`.
+    fn pixel_start(&self, value: f64) -> f64 {
+        match self.interval {
+            IntervalMode::HalfOpen => value.floor(),
+            IntervalMode::Closed => value.ceil() - 1.0,
+        }
+    }
+
+    /// The exclusive end index of the pixels covered by a rect ending at the fractional
+    /// pixel position `value`.
+    fn pixel_end(&self, value: f64) -> f64 {
+        match self.interval {
+            IntervalMode::HalfOpen => value.ceil(),
+            IntervalMode::Closed => value.floor() + 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoTransformError {
+    /// The geotransform can not be inverted
+    NotInvertible,
+    /// The pixel sizes or rotations of two geotransforms do not match
+    IncompatiblePixelSize,
+    /// The pixel coordinate can not be represented by the requested type
+    PixelOutOfRange,
+    /// The geotransforms do not share a common pixel grid
+    IncompatibleGrid,
+    /// No tiles were given to build a mosaic from
+    EmptyMosaic,
+    /// The dataset does not have a geotransform
+    MissingGeoTransform,
+    /// The dataset has the identity geotransform and no spatial reference, which
+    /// GDAL reports for images without georeferencing
+    LooksUngeoreferenced,
+    /// The dataset has no subdataset with the given index
+    NoSuchSubdataset(usize),
+    /// A geotransform requires exactly six coefficients
+    WrongCoefficientCount { found: usize },
+    /// An error reported by GDAL
+    Gdal(String),
+}
+
+impl fmt::Display for GeoTransformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeoTransformError::NotInvertible => write!(f, "Could not invert geotransform"),
+            GeoTransformError::IncompatiblePixelSize => write!(f, "The pixel sizes of the geotransforms do not match"),
+            GeoTransformError::PixelOutOfRange => write!(f, "The pixel coordinate is out of the range of the requested type"),
+            GeoTransformError::IncompatibleGrid => write!(f, "The geotransforms do not share a common pixel grid"),
+            GeoTransformError::EmptyMosaic => write!(f, "A mosaic requires at least one tile"),
+            GeoTransformError::MissingGeoTransform => write!(f, "The dataset does not have a geotransform"),
+            GeoTransformError::LooksUngeoreferenced => write!(f, "The dataset has an identity geotransform and no spatial reference"),
+            GeoTransformError::NoSuchSubdataset(index) => write!(f, "The dataset has no subdataset with index {}", index),
+            GeoTransformError::WrongCoefficientCount { found } => write!(f, "Expected 6 geotransform coefficients, found {}", found),
+            GeoTransformError::Gdal(msg) => write!(f, "GDAL error: {}", msg),
+        }
+    }
 }
 
-#[derive(Clone)]
+impl std::error::Error for GeoTransformError {}
+
+impl From<gdal::errors::Error> for GeoTransformError {
+    fn from(err: gdal::errors::Error) -> Self {
+        GeoTransformError::Gdal(err.to_string())
+    }
+}
+
+/// Summary of the extent and resolution of a gdal dataset, see `GeoTransformer::summary`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeoTransformSummary {
+    /// The boundingbox of the dataset
+    pub bounds: Rect<f64>,
+    /// Width and height of a pixel
+    pub resolution: (f64, f64),
+    /// Number of pixels of the dataset
+    pub pixel_count: u64,
+    /// Area of a single pixel
+    pub pixel_area: f64,
+    /// Area of the whole dataset
+    pub total_area: f64,
+    /// If the geotransform has rotation terms
+    pub has_rotation: bool,
+}
+
+/// Converts between pixel coordinates and geographical coordinates using a GDAL geotransform.
+///
+/// The transformer only holds the coefficients of the geotransform and its inverse together
+/// with the `Conventions` used for conversions and does not call into GDAL, so it is `Send`
+/// and `Sync` and can be shared between threads.
+#[derive(Debug, Clone)]
 pub struct GeoTransformer {
     geotransform: GeoTransform,
     inv_geotransform: GeoTransform,
+    conventions: Conventions,
 }
 
 impl GeoTransformer {
+    /// Create a transformer from the geotransform of the dataset.
+    pub fn from_dataset(dataset: &Dataset) -> Result<Self, GeoTransformError> {
+        GeoTransformer::try_from(dataset.geo_transform()?)
+    }
+
+    /// Create a transformer from the geotransform of the dataset, rejecting datasets which
+    /// are not georeferenced.
+    ///
+    /// In addition to the errors of `from_dataset`, this fails with `MissingGeoTransform`
+    /// when the dataset has no geotransform at all, and with `LooksUngeoreferenced` when the
+    /// geotransform is the identity `[0, 1, 0, 0, 0, 1]` and the dataset has no projection.
+    /// That is the transform GDAL falls back to for plain images, using it would treat pixel
+    /// space as geographic space.
+    pub fn from_dataset_strict(dataset: &Dataset) -> Result<Self, GeoTransformError> {
+        let geotransform = dataset.geo_transform().map_err(|_| GeoTransformError::MissingGeoTransform)?;
+        if geotransform == GeoTransformer::identity().geotransform && dataset.projection().trim().is_empty() {
+            return Err(GeoTransformError::LooksUngeoreferenced);
+        }
+        GeoTransformer::try_from(geotransform)
+    }
+
+    /// Create a transformer from the geotransform of a subdataset of the dataset at `path`,
+    /// as found in NetCDF or HDF files.
+    ///
+    /// The subdataset is looked up using the `SUBDATASET_<n>_NAME` item of the `SUBDATASETS`
+    /// metadata domain, so `subdataset_index` starts at 1 like in the output of `gdalinfo`.
+    /// The geotransform is read using `from_dataset_strict`, a subdataset without
+    /// georeferencing results in an error.
+    pub fn from_subdataset<P: AsRef<Path>>(path: P, subdataset_index: usize) -> Result<Self, GeoTransformError> {
+        let dataset = Dataset::open(path.as_ref())?;
+        let name = dataset.metadata_item(&format!("SUBDATASET_{}_NAME", subdataset_index), "SUBDATASETS")
+            .ok_or(GeoTransformError::NoSuchSubdataset(subdataset_index))?;
+        let subdataset = Dataset::open(Path::new(&name))?;
+        GeoTransformer::from_dataset_strict(&subdataset)
+    }
+
+    /// Create a transformer from the six coefficients in the order used by GDAL.
+    ///
+    /// GDAL interleaves origin and scale terms:
+    ///
+    /// ```text
+    /// x = gt[0] + col * gt[1] + row * gt[2]
+    /// y = gt[3] + col * gt[4] + row * gt[5]
+    /// ```
+    ///
+    /// This is the same as `GeoTransformer::try_from`.
+    pub fn from_gdal_order(geotransform: [f64; 6]) -> Result<Self, GeoTransformError> {
+        GeoTransformer::try_from(geotransform)
+    }
+
+    /// The transformer of the identity geotransform `[0, 1, 0, 0, 0, 1]`, mapping pixel
+    /// coordinates to the same geo-coordinates.
+    ///
+    /// The inverse is the identity as well, so this is a `const fn` and can be used for
+    /// `const` and `static` items.
+    pub const fn identity() -> GeoTransformer {
+        GeoTransformer {
+            geotransform: [0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+            inv_geotransform: [0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+            conventions: Conventions { anchor: PixelAnchor::Corner, interval: IntervalMode::HalfOpen },
+        }
+    }
+
+    /// Create a transformer from the coefficients in the order used by GDAL, as for example
+    /// parsed from a CSV row or a JSON array.
+    ///
+    /// Returns `WrongCoefficientCount` when the iterator does not yield exactly six values.
+    pub fn from_iter_checked<I: IntoIterator<Item = f64>>(iter: I) -> Result<Self, GeoTransformError> {
+        let mut iter = iter.into_iter();
+        let mut geotransform: GeoTransform = [0.0; 6];
+        for (found, coefficient) in geotransform.iter_mut().enumerate() {
+            *coefficient = iter.next().ok_or(GeoTransformError::WrongCoefficientCount { found })?;
+        }
+        let extra = iter.count();
+        if extra > 0 {
+            return Err(GeoTransformError::WrongCoefficientCount { found: geotransform.len() + extra });
+        }
+        GeoTransformer::try_from(geotransform)
+    }
+
+    /// Create a transformer from the two rows of the 2×3 affine matrix `[a b c; d e f]`.
+    ///
+    /// ```text
+    /// x = a * col + b * row + c
+    /// y = d * col + e * row + f
+    /// ```
+    ///
+    /// In GDAL order these are `[c, a, b, f, d, e]`, which is also the ordering of
+    /// the `affine` package used by `from_affine_tuple`.
+    pub fn from_matrix_row_major(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Result<Self, GeoTransformError> {
+        GeoTransformer::from_affine_tuple((a, b, c, d, e, f))
+    }
+
+    /// Create a north-up transformer mapping a raster of the given size onto `rect`.
+    ///
+    /// Pixel (0, 0) maps to the top-left corner of the rect and pixel (width, height) to
+    /// its bottom-right corner. Returns an error when the rect or the size is empty.
+    pub fn fit_to_size(rect: &Rect<f64>, size: (usize, usize)) -> Result<Self, GeoTransformError> {
+        if size.0 == 0 || size.1 == 0 {
+            return Err(GeoTransformError::NotInvertible);
+        }
+        GeoTransformer::try_from([
+            rect.min().x,
+            rect.width() / size.0 as f64,
+            0.0,
+            rect.max().y,
+            0.0,
+            -rect.height() / size.1 as f64,
+        ])
+    }
+
+    /// Approximate the georeferencing of a raster which only has RPC coefficients.
+    ///
+    /// **This is a coarse approximation.** Given the ground footprint derived from the RPCs
+    /// and the size of the image, a north-up transform is fitted to the footprint like
+    /// `fit_to_size` does. The terrain and the sensor geometry modelled by the RPCs are
+    /// ignored, so the result is only suitable for indexing and previews, not for
+    /// precise geolocation.
+    pub fn approximate_from_rpc_bbox(rpc_extent: &Rect<f64>, size: (usize, usize)) -> Result<Self, GeoTransformError> {
+        GeoTransformer::fit_to_size(rpc_extent, size)
+    }
+
+    /// Create a north-up transformer with square pixels covering the `envelope`.
+    ///
+    /// Returns the transformer and the size of the raster needed to cover the envelope.
+    /// The origin is the top-left corner of the envelope, the raster may reach beyond the
+    /// right and bottom edge when the envelope is not a multiple of the pixel size.
+    ///
+    /// `pixel_size` is given in the units of the coordinate system of the envelope. For
+    /// geographic coordinate systems these are degrees, so the pixels are square in degrees,
+    /// but not on the ground. Returns an error when the pixel size is not positive.
+    pub fn synthetic(envelope: &Rect<f64>, pixel_size: f64) -> Result<(Self, (usize, usize)), GeoTransformError> {
+        if !(pixel_size.is_finite() && pixel_size > 0.0) {
+            return Err(GeoTransformError::NotInvertible);
+        }
+        let size = (
+            ((envelope.width() / pixel_size).ceil() as usize).max(1),
+            ((envelope.height() / pixel_size).ceil() as usize).max(1),
+        );
+        let geotransformer = GeoTransformer::try_from([
+            envelope.min().x,
+            pixel_size,
+            0.0,
+            envelope.max().y,
+            0.0,
+            -pixel_size,
+        ])?;
+        Ok((geotransformer, size))
+    }
+
     /// Convert a coordinate to the pixel coordinate in the dataset.
     ///
     /// Will return pixel coordinates outside of the bounds of the dataset when
     /// the coordinates are outside of the envelope of the raster.
     pub fn coordinate_to_pixel(&self, coordinate: Coordinate<f64>) -> (usize, usize) {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        (pixel.0.floor() as usize, pixel.1.floor() as usize)
+    }
+
+    /// Convert a coordinate to the signed pixel coordinate in the dataset.
+    ///
+    /// Coordinates above or left of the origin of the dataset result in negative pixel coordinates.
+    pub fn coordinate_to_pixel_signed(&self, coordinate: Coordinate<f64>) -> (i64, i64) {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        (pixel.0.floor() as i64, pixel.1.floor() as i64)
+    }
+
+    /// Convert a coordinate to the pixel coordinate in the dataset using the integer type `T`.
+    ///
+    /// Returns an error when the pixel coordinate does not fit into `T`, e.g. when it
+    /// is negative and `T` is unsigned, or when the coordinate is not finite.
+    pub fn coordinate_to_pixel_as<T: TryFrom<i64>>(&self, coordinate: Coordinate<f64>) -> Result<(T, T), GeoTransformError> {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        let convert = |value: f64| {
+            let value = value.floor();
+            // i64::MAX as f64 rounds up to 2^63, which is out of range itself
+            if value >= i64::MIN as f64 && value < i64::MAX as f64 {
+                T::try_from(value as i64).map_err(|_| GeoTransformError::PixelOutOfRange)
+            } else {
+                Err(GeoTransformError::PixelOutOfRange)
+            }
+        };
+        Ok((convert(pixel.0)?, convert(pixel.1)?))
+    }
+
+    /// Convert a coordinate to the pixel coordinate in a dataset of the given size.
+    ///
+    /// Returns `None` when the coordinate is not finite or outside of the dataset. Whether
+    /// the right and bottom edges of the dataset are inside depends on the `IntervalMode`
+    /// of the conventions.
+    pub fn coordinate_to_pixel_checked(&self, coordinate: Coordinate<f64>, size: (usize, usize)) -> Option<(usize, usize)> {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        let col = self.conventions.pixel_index(pixel.0, size.0);
+        let row = self.conventions.pixel_index(pixel.1, size.1);
+        if col >= 0.0 && row >= 0.0 && col < size.0 as f64 && row < size.1 as f64 {
+            Some((col as usize, row as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Convert all coordinates to pixel coordinates in a dataset of the given size.
+    ///
+    /// Same as `coordinate_to_pixel_checked`, with `None` for each coordinate which is
+    /// not finite or outside of the dataset.
+    pub fn coordinates_to_pixels_checked(&self, coords: &[Coordinate<f64>], size: (usize, usize)) -> Vec<Option<(usize, usize)>> {
+        coords.iter()
+            .map(|coordinate| self.coordinate_to_pixel_checked(*coordinate, size))
+            .collect()
+    }
+
+    /// Convert geo-coordinates stored as separate slices of x and y values to fractional
+    /// pixel positions in place.
+    ///
+    /// After the call `xs` holds the columns and `ys` the rows. Nothing is allocated, which
+    /// makes this suitable for large columnar buffers.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the slices differ in length.
+    pub fn transform_coords_in_place(&self, xs: &mut [f64], ys: &mut [f64]) {
+        assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+        let inv = &self.inv_geotransform;
+        for (x, y) in xs.iter_mut().zip(ys.iter_mut()) {
+            let (geo_x, geo_y) = (*x, *y);
+            *x = inv[0] + inv[1] * geo_x + inv[2] * geo_y;
+            *y = inv[3] + inv[4] * geo_x + inv[5] * geo_y;
+        }
+    }
+
+    /// Convert a point to the signed pixel coordinate in the dataset, see `coordinate_to_pixel_signed`.
+    pub fn point_to_pixel(&self, point: Point<f64>) -> (i64, i64) {
+        self.coordinate_to_pixel_signed(point.0)
+    }
+
+    /// Convert a pixel coordinate to the point of the pixel, see `pixel_to_coordinate`.
+    pub fn pixel_to_point(&self, pixel: (usize, usize)) -> Point<f64> {
+        Point(self.pixel_to_coordinate(pixel))
+    }
+
+    /// Convert a fractional pixel position to a geo-coordinate of any type constructible
+    /// from an `(x, y)` tuple.
+    ///
+    /// This allows converting to and from geometry types other than those of `geo-types`
+    /// without an intermediate `Coordinate`. The position is not rounded, so `(0.5, 0.5)`
+    /// is the center of the top-left pixel.
+    pub fn apply_pixel_to_coord<P: Into<(f64, f64)>, C: From<(f64, f64)>>(&self, pixel: P) -> C {
+        self.fractional_pixel_to_coordinate(pixel.into()).x_y().into()
+    }
+
+    /// Convert a geo-coordinate of any type convertible to an `(x, y)` tuple to the
+    /// fractional pixel position, the inverse of `apply_pixel_to_coord`.
+    pub fn apply_coord_to_pixel<C: Into<(f64, f64)>, P: From<(f64, f64)>>(&self, coordinate: C) -> P {
+        let (x, y) = coordinate.into();
+        self.coordinate_to_fractional_pixel(Coordinate { x, y }).into()
+    }
+
+    /// Convert a coordinate to the signed pixel coordinate, snapping to pixel boundaries.
+    ///
+    /// When the fractional pixel position is within `eps` pixels of an integer, it is snapped
+    /// to that integer before flooring. This avoids off-by-one results for coordinates exactly
+    /// on a pixel boundary, like those created by `pixel_to_coordinate`, which may end up
+    /// slightly below the boundary due to floating point errors.
+    pub fn coordinate_to_pixel_snapped(&self, coordinate: Coordinate<f64>, eps: f64) -> (i64, i64) {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        let snap = |value: f64| {
+            let rounded = value.round();
+            if (value - rounded).abs() <= eps { rounded } else { value.floor() }
+        };
+        (snap(pixel.0) as i64, snap(pixel.1) as i64)
+    }
+
+    /// The index of the block of the given size which contains the coordinate.
+    ///
+    /// The pixel coordinate is divided by the block size rounding towards negative
+    /// infinity, so coordinates above or left of the origin of the dataset result in
+    /// negative block indexes: pixel -1 is in block -1, not in block 0 as truncating
+    /// integer division would give. `NaN` coordinates are not detected, they convert
+    /// to pixel 0 and so end up in block 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the width or the height of `block_size` is zero.
+    pub fn coordinate_to_block_index(&self, coordinate: Coordinate<f64>, block_size: (usize, usize)) -> (i64, i64) {
+        let pixel = self.coordinate_to_pixel_signed(coordinate);
+        (pixel.0.div_euclid(block_size.0 as i64), pixel.1.div_euclid(block_size.1 as i64))
+    }
+
+    /// Convert a coordinate to the fractional pixel position in the dataset
+    fn coordinate_to_fractional_pixel(&self, coordinate: Coordinate<f64>) -> (f64, f64) {
         // ported from https://github.com/OSGeo/gdal/blob/master/gdal/apps/gdallocationinfo.cpp#L282
         (
-            (self.inv_geotransform[0] + (self.inv_geotransform[1] * coordinate.x) + (self.inv_geotransform[2] * coordinate.y)).floor() as usize,
-            (self.inv_geotransform[3] + (self.inv_geotransform[4] * coordinate.x) + (self.inv_geotransform[5] * coordinate.y)).floor() as usize
+            self.inv_geotransform[0] + (self.inv_geotransform[1] * coordinate.x) + (self.inv_geotransform[2] * coordinate.y),
+            self.inv_geotransform[3] + (self.inv_geotransform[4] * coordinate.x) + (self.inv_geotransform[5] * coordinate.y)
         )
     }
 
-    /// Convert a pixel coordinate to the geo-coordinate
+    /// Convert a pixel coordinate to the geo-coordinate of the pixel.
+    ///
+    /// This is the top-left corner of the pixel, or the location given by the `PixelAnchor`
+    /// of the conventions.
     pub fn pixel_to_coordinate(&self, pixel: (usize, usize)) -> Coordinate<f64> {
+        self.pixel_to_coordinate_anchored(pixel, self.conventions.anchor)
+    }
+
+    /// Convert a pixel coordinate to the geo-coordinate of the given `anchor` within the pixel
+    pub fn pixel_to_coordinate_anchored(&self, pixel: (usize, usize), anchor: PixelAnchor) -> Coordinate<f64> {
+        let offset = anchor.offset();
+        self.fractional_pixel_to_coordinate((pixel.0 as f64 + offset, pixel.1 as f64 + offset))
+    }
+
+    /// The geo-coordinate of the top-left corner of the pixel, regardless of the conventions.
+    fn pixel_corner(&self, pixel: (usize, usize)) -> Coordinate<f64> {
+        self.pixel_to_coordinate_anchored(pixel, PixelAnchor::Corner)
+    }
+
+    /// The coordinate at the relative position `fx`, `fy` within a dataset of the given size.
+    ///
+    /// `(0.0, 0.0)` is the top-left corner and `(1.0, 1.0)` the bottom-right corner of the
+    /// dataset in pixel space.
+    pub fn coordinate_at_fraction(&self, fx: f64, fy: f64, size: (usize, usize)) -> Coordinate<f64> {
+        self.fractional_pixel_to_coordinate((fx * size.0 as f64, fy * size.1 as f64))
+    }
+
+    /// Convert a fractional pixel position to the geo-coordinate
+    fn fractional_pixel_to_coordinate(&self, pixel: (f64, f64)) -> Coordinate<f64> {
         // ported form https://github.com/OSGeo/gdal/blob/18bfbd32302f611bde0832f61ca0747d4c4421dd/gdal/apps/gdalinfo_lib.cpp#L1443
         Coordinate {
-            x: self.geotransform[0] + (self.geotransform[1] * pixel.0 as f64) + (self.geotransform[2] * pixel.1 as f64),
-            y: self.geotransform[3] + (self.geotransform[4] * pixel.0 as f64) + (self.geotransform[5] * pixel.1 as f64),
+            x: self.geotransform[0] + (self.geotransform[1] * pixel.0) + (self.geotransform[2] * pixel.1),
+            y: self.geotransform[3] + (self.geotransform[4] * pixel.0) + (self.geotransform[5] * pixel.1),
         }
     }
 
     /// generate to boundingbox from the size of a gdal dataset
+    ///
+    /// The boundingbox is the envelope of all four corners, so it also covers rotated rasters.
     pub fn bounds_from_size(&self, size: (usize, usize)) -> Rect<f64> {
-        let c1 = self.pixel_to_coordinate((0, 0));
-        let c2 = self.pixel_to_coordinate(size);
-        rect_from_coordinates(c1, c2)
+        self.envelope_of_pixel_rect((0.0, 0.0), (size.0 as f64, size.1 as f64))
     }
-}
 
-impl TryFrom<GeoTransform> for GeoTransformer {
-    type Error = &'static str;
+    /// The envelope of the geo-coordinates of the rectangle between two pixel positions.
+    ///
+    /// All four corners of the rectangle are transformed, so this is also correct for
+    /// rotated rasters.
+    pub fn envelope_of_pixel_rect(&self, min_pixel: (f64, f64), max_pixel: (f64, f64)) -> Rect<f64> {
+        let corners = [
+            self.fractional_pixel_to_coordinate(min_pixel),
+            self.fractional_pixel_to_coordinate((max_pixel.0, min_pixel.1)),
+            self.fractional_pixel_to_coordinate(max_pixel),
+            self.fractional_pixel_to_coordinate((min_pixel.0, max_pixel.1)),
+        ];
+        envelope_of_coordinates(corners.iter().copied())
+    }
 
-    fn try_from(geotransform: GeoTransform) -> Result<Self, Self::Error> {
-        let mut inv_geotransform = GeoTransform::default();
-        let mut gt = geotransform;
-        let res = unsafe { gdal_sys::GDALInvGeoTransform(gt.as_mut_ptr(), inv_geotransform.as_mut_ptr()) };
-        if res == 0 {
-            Err("Could not invert geotransform")
-        } else {
-            Ok(GeoTransformer { geotransform: gt, inv_geotransform })
-        }
+    /// The four corner coordinates of a gdal dataset of the given size.
+    ///
+    /// The corners are returned in the order `[top_left, top_right, bottom_right, bottom_left]`
+    /// of the pixel space, which may differ from the geographic orientation for flipped or
+    /// rotated rasters.
+    pub fn corners(&self, size: (usize, usize)) -> [Coordinate<f64>; 4] {
+        [
+            self.pixel_corner((0, 0)),
+            self.pixel_corner((size.0, 0)),
+            self.pixel_corner(size),
+            self.pixel_corner((0, size.1)),
+        ]
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::convert::TryFrom;
-    use std::path::Path;
+    /// The outline of a gdal dataset of the given size as a polygon.
+    pub fn footprint_polygon(&self, size: (usize, usize)) -> Polygon<f64> {
+        self.footprint_polygon_densified(size, 0)
+    }
+
+    /// The outline of a gdal dataset of the given size as a polygon with `points_per_edge`
+    /// additional vertices inserted along each edge.
+    ///
+    /// The vertices are inserted in pixel space, so after reprojecting the polygon to
+    /// another coordinate system it still follows the curved edges of the raster. GDAL
+    /// itself uses 21 points per edge when transforming bounds.
+    pub fn footprint_polygon_densified(&self, size: (usize, usize), points_per_edge: usize) -> Polygon<f64> {
+        let exterior: Vec<Coordinate<f64>> = densified_outline_pixels(size, points_per_edge)
+            .into_iter()
+            .map(|pixel| self.fractional_pixel_to_coordinate(pixel))
+            .collect();
+        Polygon::new(LineString::from(exterior), vec![])
+    }
 
-    use gdal::raster::Dataset;
-    use geo_types::Coordinate;
+    /// The center coordinates of every `step`-th pixel along the outer ring of pixels of
+    /// a dataset of the given size.
+    ///
+    /// The ring is walked once clockwise in pixel space, starting at the top-left pixel.
+    /// A `step` of zero is treated as one.
+    pub fn boundary_coordinates(&self, size: (usize, usize), step: usize) -> impl Iterator<Item = Coordinate<f64>> + '_ {
+        let (width, height) = size;
+        let count = match (width, height) {
+            (0, _) | (_, 0) => 0,
+            (_, 1) => width,
+            (1, _) => height,
+            _ => 2 * width + 2 * height - 4,
+        };
+        let ring_pixel = move |i: usize| {
+            if height == 1 {
+                (i, 0)
+            } else if width == 1 {
+                (0, i)
+            } else if i < width {
+                (i, 0)
+            } else if i < width + height - 1 {
+                (width - 1, i + 1 - width)
+            } else if i < 2 * width + height - 2 {
+                (2 * width + height - 3 - i, height - 1)
+            } else {
+                (0, 2 * width + 2 * height - 4 - i)
+            }
+        };
+        (0..count)
+            .step_by(step.max(1))
+            .map(move |i| self.pixel_to_coordinate_anchored(ring_pixel(i), PixelAnchor::Center))
+    }
 
-    use crate::GeoTransformer;
+    /// The pixels crossed by the line between two coordinates, including the pixels of
+    /// both endpoints.
+    ///
+    /// The endpoints are converted using `coordinate_to_pixel_signed` and connected using
+    /// Bresenham's line algorithm, so each step moves to one of the eight neighbouring
+    /// pixels. Pixels outside of the dataset are yielded as well.
+    ///
+    /// The iterator is empty when an endpoint is not finite or more than 2^60 pixels away
+    /// from the origin, as the traversal could overflow `i64` for such endpoints.
+    pub fn pixels_along_line(&self, start: Coordinate<f64>, end: Coordinate<f64>) -> impl Iterator<Item = (i64, i64)> {
+        const LIMIT: f64 = (1u64 << 60) as f64;
+        let endpoint = |coordinate: Coordinate<f64>| {
+            let pixel = self.coordinate_to_fractional_pixel(coordinate);
+            let (col, row) = (pixel.0.floor(), pixel.1.floor());
+            if col.abs() <= LIMIT && row.abs() <= LIMIT {
+                Some((col as i64, row as i64))
+            } else {
+                // also the case for NaN
+                None
+            }
+        };
+        let (start, end) = (endpoint(start), endpoint(end));
+        let mut done = start.is_none() || end.is_none();
+        let (mut x, mut y) = start.unwrap_or((0, 0));
+        let (x1, y1) = end.unwrap_or((0, 0));
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut err = dx + dy;
 
-    macro_rules! assert_coordinates_relative_eq {
-        ($given:expr, $expected:expr) => {
-            assert_relative_eq!($given.x, $expected.x, epsilon = 0.000001);
-            assert_relative_eq!($given.y, $expected.y, epsilon = 0.000001);
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let pixel = (x, y);
+            if pixel == (x1, y1) {
+                done = true;
+            } else {
+                let err2 = 2 * err;
+                if err2 >= dy {
+                    err += dy;
+                    x += sx;
+                }
+                if err2 <= dx {
+                    err += dx;
+                    y += sy;
+                }
+            }
+            Some(pixel)
+        })
+    }
+
+    /// The line through the centers of the pixels of a row of a dataset with `width` columns.
+    pub fn row_linestring(&self, row: usize, width: usize) -> LineString<f64> {
+        (0..width)
+            .map(|col| self.pixel_to_coordinate_anchored((col, row), PixelAnchor::Center))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// The line through the centers of the pixels of a column of a dataset with `height` rows.
+    pub fn column_linestring(&self, col: usize, height: usize) -> LineString<f64> {
+        (0..height)
+            .map(|row| self.pixel_to_coordinate_anchored((col, row), PixelAnchor::Center))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Width and height of a pixel in the units of the coordinate system.
+    ///
+    /// These are the lengths of the pixel edges, so they are positive and also correct
+    /// for rotated rasters.
+    pub fn resolution(&self) -> (f64, f64) {
+        let gt = &self.geotransform;
+        (gt[1].hypot(gt[4]), gt[2].hypot(gt[5]))
+    }
+
+    /// Area of a single pixel in the squared units of the coordinate system.
+    pub fn pixel_area(&self) -> f64 {
+        let gt = &self.geotransform;
+        (gt[1] * gt[5] - gt[2] * gt[4]).abs()
+    }
+
+    /// Check if the geotransform has rotation terms, so the raster is not north-up.
+    pub fn has_rotation(&self) -> bool {
+        self.geotransform[2] != 0.0 || self.geotransform[4] != 0.0
+    }
+
+    /// Summarize extent and resolution of a gdal dataset of the given size.
+    pub fn summary(&self, size: (usize, usize)) -> GeoTransformSummary {
+        let pixel_count = size.0 as u64 * size.1 as u64;
+        GeoTransformSummary {
+            bounds: self.bounds_from_size(size),
+            resolution: self.resolution(),
+            pixel_count,
+            pixel_area: self.pixel_area(),
+            total_area: self.pixel_area() * pixel_count as f64,
+            has_rotation: self.has_rotation(),
         }
     }
 
+    /// The distance between the top-left and the bottom-right corner of a dataset of the given size.
+    ///
+    /// The distance is computed from the transformed corners, so unlike computing it from
+    /// `gt[1]` and `gt[5]` alone it is also correct for rotated rasters.
+    pub fn diagonal_ground_length(&self, size: (usize, usize)) -> f64 {
+        let top_left = self.pixel_corner((0, 0));
+        let bottom_right = self.pixel_corner(size);
+        (bottom_right.x - top_left.x).hypot(bottom_right.y - top_left.y)
+    }
 
-    fn open_dataset(dataset_filename: &str) -> (Dataset, GeoTransformer) {
-        let path = Path::new(dataset_filename);
-        let dataset = Dataset::open(path).unwrap();
+    /// Check if the pixel is within a dataset of the given size.
+    pub fn contains_pixel(&self, pixel: (i64, i64), size: (usize, usize)) -> bool {
+        pixel.0 >= 0 && pixel.1 >= 0 && (pixel.0 as u64) < size.0 as u64 && (pixel.1 as u64) < size.1 as u64
+    }
 
-        let geotransform = dataset.geo_transform().unwrap();
-        let geotransformer = GeoTransformer::try_from(geotransform).unwrap();
+    /// Check if a kernel of `half_window` pixels around the pixel of the coordinate fits
+    /// into a dataset of the given size.
+    ///
+    /// The kernel covers the columns `col - half_window ..= col + half_window` and the rows
+    /// `row - half_window ..= row + half_window`. Coordinates which are not finite or so far
+    /// away that the kernel can not be represented do not fit.
+    pub fn kernel_fits(&self, coordinate: Coordinate<f64>, half_window: usize, size: (usize, usize)) -> bool {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        if !(pixel.0.is_finite() && pixel.1.is_finite()) {
+            return false;
+        }
+        let col = self.conventions.pixel_index(pixel.0, size.0) as i64;
+        let row = self.conventions.pixel_index(pixel.1, size.1) as i64;
+        let half = match i64::try_from(half_window) {
+            Ok(half) => half,
+            Err(_) => return false,
+        };
+        let kernel = (col.checked_sub(half), row.checked_sub(half), col.checked_add(half), row.checked_add(half));
+        match kernel {
+            (Some(min_col), Some(min_row), Some(max_col), Some(max_row)) => {
+                self.contains_pixel((min_col, min_row), size) && self.contains_pixel((max_col, max_row), size)
+            }
+            _ => false,
+        }
+    }
 
-        (dataset, geotransformer)
+    /// generate the boundingbox in pixel space from the size of a gdal dataset
+    pub fn pixel_bounds(&self, size: (usize, usize)) -> Rect<f64> {
+        Rect::new(
+            Coordinate { x: 0.0, y: 0.0 },
+            Coordinate { x: size.0 as f64, y: size.1 as f64 },
+        )
     }
 
-    #[test]
-    fn test_geotransformer_bounds() {
-        let (dataset, geotransformer) = open_dataset("data/small.tiff");
-        let bounds = geotransformer.bounds_from_size(dataset.size());
+    /// The number of columns and rows the `rect` spans in the dataset.
+    ///
+    /// The corners of the rect are converted to fractional pixel positions, so for rotated
+    /// rasters this is the extent of the rect along the column and row axes.
+    pub fn rect_pixel_span(&self, rect: &Rect<f64>) -> (f64, f64) {
+        let (min, max) = (rect.min(), rect.max());
+        let pixels = [
+            self.coordinate_to_fractional_pixel(min),
+            self.coordinate_to_fractional_pixel(Coordinate { x: max.x, y: min.y }),
+            self.coordinate_to_fractional_pixel(max),
+            self.coordinate_to_fractional_pixel(Coordinate { x: min.x, y: max.y }),
+        ];
+        let span = |axis: fn(&(f64, f64)) -> f64| {
+            let values = pixels.iter().map(axis);
+            values.clone().fold(f64::NEG_INFINITY, f64::max) - values.fold(f64::INFINITY, f64::min)
+        };
+        (span(|p| p.0), span(|p| p.1))
+    }
 
-        // expected values where collected by using gdalinfo
-        assert_coordinates_relative_eq!(bounds.min(), Coordinate {
-            x: 11.3610659,
-            y: 32.2014463,
-        });
-        assert_coordinates_relative_eq!(bounds.max(), Coordinate {
-            x: 28.2838457,
-            y: 46.2520256,
-        });
+    /// The window of pixels of a dataset of the given size covering the `rect`.
+    ///
+    /// The window is clamped to the dataset, `None` is returned when the rect does not
+    /// overlap the dataset. Whether pixels only touched by the edges of the rect are
+    /// covered depends on the `IntervalMode` of the conventions.
+    pub fn rect_to_window(&self, rect: &Rect<f64>, size: (usize, usize)) -> Option<Window> {
+        let (min, max) = (rect.min(), rect.max());
+        let pixels = [
+            self.coordinate_to_fractional_pixel(min),
+            self.coordinate_to_fractional_pixel(Coordinate { x: max.x, y: min.y }),
+            self.coordinate_to_fractional_pixel(max),
+            self.coordinate_to_fractional_pixel(Coordinate { x: min.x, y: max.y }),
+        ];
+        let min_col = self.conventions.pixel_start(pixels.iter().map(|p| p.0).fold(f64::INFINITY, f64::min));
+        let max_col = self.conventions.pixel_end(pixels.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max));
+        let min_row = self.conventions.pixel_start(pixels.iter().map(|p| p.1).fold(f64::INFINITY, f64::min));
+        let max_row = self.conventions.pixel_end(pixels.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max));
+        Window::from_pixel_bounds((min_col, min_row), (max_col, max_row), size)
     }
 
+    /// The envelope of the geo-coordinates of the window.
+    pub fn window_to_rect(&self, window: &Window) -> Rect<f64> {
+        let min = (window.offset.0 as f64, window.offset.1 as f64);
+        let max = (min.0 + window.size.0 as f64, min.1 + window.size.1 as f64);
+        self.envelope_of_pixel_rect(min, max)
+    }
 
-    #[test]
-    fn test_geotransformer_coordinate_to_pixel() {
-        let (dataset, geotransformer) = open_dataset("data/small.tiff");
-        let bounds = geotransformer.bounds_from_size(dataset.size());
+    /// Width and height of the window in the units of the coordinate system.
+    ///
+    /// These are the lengths of the edges of the window along the pixel axes. For rotated
+    /// rasters they differ from the width and height of `window_to_rect`, which is the
+    /// axis-aligned envelope of the window.
+    pub fn window_ground_size(&self, window: &Window) -> (f64, f64) {
+        let resolution = self.resolution();
+        (window.size.0 as f64 * resolution.0, window.size.1 as f64 * resolution.1)
+    }
 
-        let c1 = geotransformer.coordinate_to_pixel(bounds.min());
-        assert_eq!(c1, (0, 44));
-        let c2 = geotransformer.coordinate_to_pixel(bounds.max());
-        assert_eq!(c2, (52, 0));
+    /// The smallest window of pixels of a dataset of the given size containing all `coords`.
+    ///
+    /// The window is clamped to the dataset, `None` is returned when it does not overlap
+    /// the dataset. Non-finite coordinates are ignored.
+    pub fn enclosing_window(&self, coords: &[Coordinate<f64>], size: (usize, usize)) -> Option<Window> {
+        let (min, max) = coords.iter()
+            .map(|c| self.coordinate_to_fractional_pixel(*c))
+            .filter(|p| p.0.is_finite() && p.1.is_finite())
+            .map(|p| (self.conventions.pixel_index(p.0, size.0), self.conventions.pixel_index(p.1, size.1)))
+            .fold(None, |acc: Option<((f64, f64), (f64, f64))>, p| match acc {
+                None => Some((p, p)),
+                Some((min, max)) => Some(((min.0.min(p.0), min.1.min(p.1)), (max.0.max(p.0), max.1.max(p.1)))),
+            })?;
+        Window::from_pixel_bounds(min, (max.0 + 1.0, max.1 + 1.0), size)
+    }
+
+    /// Create a transformer for a window of the dataset starting at the pixel `offset`.
+    ///
+    /// The origin of the returned transformer is moved to the top-left corner of the
+    /// window, pixel size and rotation are kept.
+    pub fn for_window(&self, offset: (usize, usize)) -> GeoTransformer {
+        let origin = self.pixel_corner(offset);
+        let mut geotransform = self.geotransform;
+        geotransform[0] = origin.x;
+        geotransform[3] = origin.y;
+        self.derive(geotransform)
+    }
+
+    /// Create a transformer for the dataset with a border of `border` pixels added on all sides.
+    ///
+    /// The origin is moved `border` pixels up and left along the pixel axes, pixel size and
+    /// rotation are kept. Use `size_with_border` for the size of the bordered dataset.
+    pub fn with_border(&self, border: usize) -> GeoTransformer {
+        let origin = self.fractional_pixel_to_coordinate((-(border as f64), -(border as f64)));
+        let mut geotransform = self.geotransform;
+        geotransform[0] = origin.x;
+        geotransform[3] = origin.y;
+        self.derive(geotransform)
+    }
+
+    /// The size of a dataset of the given size after adding a border of `border` pixels on all sides.
+    pub fn size_with_border(&self, size: (usize, usize), border: usize) -> (usize, usize) {
+        (size.0 + 2 * border, size.1 + 2 * border)
+    }
+
+    /// Create a transformer for a block of the dataset.
+    ///
+    /// The block is addressed by its `block_index` the same way GDALs `read_block` does, so
+    /// the pixel coordinates of the returned transformer are relative to the top-left
+    /// corner of the block.
+    pub fn for_block(&self, block_index: (usize, usize), block_size: (usize, usize)) -> GeoTransformer {
+        self.for_window((block_index.0 * block_size.0, block_index.1 * block_size.1))
+    }
+
+    /// Mirror the transformer vertically for a raster with `height` rows.
+    ///
+    /// The origin is moved to the bottom-left corner of the raster and the row axis
+    /// is reversed, so the first row of the flipped raster is the last row of the original.
+    pub fn flip_vertical(&self, height: usize) -> GeoTransformer {
+        let origin = self.pixel_corner((0, height));
+        let mut geotransform = self.geotransform;
+        geotransform[0] = origin.x;
+        geotransform[2] = -geotransform[2];
+        geotransform[3] = origin.y;
+        geotransform[5] = -geotransform[5];
+        self.derive(geotransform)
+    }
+
+    /// Mirror the transformer horizontally for a raster with `width` columns.
+    ///
+    /// The origin is moved to the top-right corner of the raster and the column axis
+    /// is reversed, so the first column of the flipped raster is the last column of the original.
+    pub fn flip_horizontal(&self, width: usize) -> GeoTransformer {
+        let origin = self.pixel_corner((width, 0));
+        let mut geotransform = self.geotransform;
+        geotransform[0] = origin.x;
+        geotransform[1] = -geotransform[1];
+        geotransform[3] = origin.y;
+        geotransform[4] = -geotransform[4];
+        self.derive(geotransform)
+    }
+
+    /// Move the origin of the transformer to the nearest grid node of the `reference` transformer.
+    ///
+    /// Pixel size and rotation are kept, so after aligning the pixels of both rasters
+    /// line up pixel-for-pixel. Returns an error when the pixel sizes or rotations of
+    /// both transformers differ.
+    pub fn aligned_to(&self, reference: &GeoTransformer) -> Result<GeoTransformer, GeoTransformError> {
+        if !self.has_same_pixel_size(reference, PIXEL_SIZE_TOLERANCE) {
+            return Err(GeoTransformError::IncompatiblePixelSize);
+        }
+        let offset = reference.coordinate_to_fractional_pixel(self.pixel_corner((0, 0)));
+        let origin = reference.fractional_pixel_to_coordinate((offset.0.round(), offset.1.round()));
+        let mut geotransform = self.geotransform;
+        geotransform[0] = origin.x;
+        geotransform[3] = origin.y;
+        Ok(self.derive(geotransform))
+    }
+
+    /// Check if the pixel size and rotation terms of both transformers match
+    /// within `tolerance` relative to the largest term.
+    fn has_same_pixel_size(&self, other: &GeoTransformer, tolerance: f64) -> bool {
+        let magnitude = [1, 2, 4, 5].iter()
+            .map(|&i| self.geotransform[i].abs())
+            .fold(0.0, f64::max);
+        [1, 2, 4, 5].iter()
+            .all(|&i| (self.geotransform[i] - other.geotransform[i]).abs() <= tolerance * magnitude)
+    }
+
+    /// Check if both transformers describe the same grid.
+    ///
+    /// This is the case when pixel size and rotation match and the origins are apart by
+    /// an integer number of pixels. `eps` is given in pixels: the pixel size terms may differ
+    /// by `eps` relative to the pixel size, and the origin offset may be `eps` pixels
+    /// away from an integer.
+    pub fn is_grid_compatible(&self, other: &GeoTransformer, eps: f64) -> bool {
+        if !self.has_same_pixel_size(other, eps) {
+            return false;
+        }
+        let offset = self.coordinate_to_fractional_pixel(other.pixel_corner((0, 0)));
+        (offset.0 - offset.0.round()).abs() <= eps && (offset.1 - offset.1.round()).abs() <= eps
+    }
+
+    /// The position of the origin of `other` in the pixel grid of this transformer.
+    ///
+    /// Returns `None` when the grids are not compatible according to `is_grid_compatible`.
+    pub fn grid_offset(&self, other: &GeoTransformer) -> Option<(i64, i64)> {
+        if !self.is_grid_compatible(other, PIXEL_SIZE_TOLERANCE) {
+            return None;
+        }
+        let offset = self.coordinate_to_fractional_pixel(other.pixel_corner((0, 0)));
+        Some((offset.0.round() as i64, offset.1.round() as i64))
+    }
+
+    /// Rotate the raster counter-clockwise by `angle_radians` about the `pivot_pixel`.
+    ///
+    /// The rotation is applied to the geographic orientation of the pixel axes, while the
+    /// pivot keeps mapping to the same coordinate. Returns an error when the angle or the
+    /// pivot is not finite, as the resulting geotransform would not be usable.
+    pub fn rotated_about(&self, angle_radians: f64, pivot_pixel: (f64, f64)) -> Result<GeoTransformer, GeoTransformError> {
+        let pivot = self.fractional_pixel_to_coordinate(pivot_pixel);
+        let (sin, cos) = angle_radians.sin_cos();
+        let gt = &self.geotransform;
+
+        let mut geotransform = [
+            0.0,
+            cos * gt[1] - sin * gt[4],
+            cos * gt[2] - sin * gt[5],
+            0.0,
+            sin * gt[1] + cos * gt[4],
+            sin * gt[2] + cos * gt[5],
+        ];
+        geotransform[0] = pivot.x - geotransform[1] * pivot_pixel.0 - geotransform[2] * pivot_pixel.1;
+        geotransform[3] = pivot.y - geotransform[4] * pivot_pixel.0 - geotransform[5] * pivot_pixel.1;
+        if !geotransform.iter().all(|value| value.is_finite()) {
+            return Err(GeoTransformError::NotInvertible);
+        }
+        self.try_derive(geotransform)
+    }
+
+    /// The fractional part of the position of the origin of `other` in the pixel grid of
+    /// this transformer.
+    ///
+    /// This is what is left after removing the integer offset as returned by `grid_offset`,
+    /// so both values are in the range `[-0.5, 0.5]`. The pixel sizes of both transformers
+    /// are not compared, use `is_grid_compatible` for that.
+    pub fn subpixel_offset(&self, other: &GeoTransformer) -> (f64, f64) {
+        let offset = self.coordinate_to_fractional_pixel(other.pixel_corner((0, 0)));
+        (offset.0 - offset.0.round(), offset.1 - offset.1.round())
+    }
+
+    /// Reproject the bounds of a dataset of the given size using a `proj` transformation.
+    ///
+    /// The outline of the dataset is densified before it is transformed, so the
+    /// returned envelope also covers the bulges of non-linear projections.
+    #[cfg(feature = "proj")]
+    pub fn reproject_bounds_proj(&self, size: (usize, usize), proj: &proj::Proj) -> Result<Rect<f64>, proj::ProjError> {
+        let mut points: Vec<geo_types::Point<f64>> = densified_outline_pixels(size, REPROJECTION_POINTS_PER_EDGE)
+            .into_iter()
+            .map(|pixel| self.fractional_pixel_to_coordinate(pixel).into())
+            .collect();
+        proj.convert_array(&mut points)?;
+        Ok(envelope_of_coordinates(points.into_iter().map(|point| point.0)))
+    }
+
+    /// Suggest the transformer and size of the output of warping a dataset of the given size
+    /// from the `source` to the `target` coordinate system.
+    ///
+    /// This follows the heuristic of `GDALSuggestedWarpOutput`: the densified outline of the
+    /// dataset is transformed to get the output extent, and the resolution is chosen to keep
+    /// the number of pixels along the diagonal of the extent. The output pixels are square
+    /// and north-up. Note that the axis order of the coordinate systems is used as reported
+    /// by GDAL, e.g. latitude first for `EPSG:4326` with GDAL 3.
+    ///
+    /// Returns `NotInvertible` for an empty dataset or when the transformed extent has no
+    /// area, as no resolution can be derived then. The conventions of `self` are kept.
+    pub fn suggest_warp_output(&self, size: (usize, usize), source: &SpatialRef, target: &SpatialRef) -> Result<(GeoTransformer, (usize, usize)), GeoTransformError> {
+        let diagonal_pixels = (size.0 as f64).hypot(size.1 as f64);
+        if diagonal_pixels == 0.0 {
+            return Err(GeoTransformError::NotInvertible);
+        }
+        let (mut xs, mut ys): (Vec<f64>, Vec<f64>) = densified_outline_pixels(size, REPROJECTION_POINTS_PER_EDGE)
+            .into_iter()
+            .map(|pixel| self.fractional_pixel_to_coordinate(pixel).x_y())
+            .unzip();
+        let mut zs = vec![0.0; xs.len()];
+        CoordTransform::new(source, target)?.transform_coords(&mut xs, &mut ys, &mut zs)?;
+
+        let extent = envelope_of_coordinates(xs.into_iter().zip(ys).map(Coordinate::from));
+        let diagonal = extent.width().hypot(extent.height());
+        let resolution = diagonal / diagonal_pixels;
+        if !(resolution.is_finite() && resolution > 0.0) {
+            return Err(GeoTransformError::NotInvertible);
+        }
+
+        let geotransformer = self.try_derive([extent.min().x, resolution, 0.0, extent.max().y, 0.0, -resolution])?;
+        let output_size = (
+            (extent.width() / resolution + 0.5) as usize,
+            (extent.height() / resolution + 0.5) as usize,
+        );
+        Ok((geotransformer, output_size))
+    }
+
+    /// Round all coefficients of the geotransform to `decimals` decimal places.
+    ///
+    /// Gives a canonical representative for transformers which only differ by floating
+    /// point noise. Returns an error when the rounded geotransform is not invertible
+    /// anymore, e.g. when the pixel size rounds to zero. Coefficients which can not be
+    /// scaled to `decimals` places without losing precision, or overflowing for a large
+    /// `decimals`, are already at least that precise and are kept unchanged.
+    pub fn quantized(&self, decimals: u32) -> Result<GeoTransformer, GeoTransformError> {
+        // from 2^52 on all f64 values are integers, so there is nothing left to round
+        const INTEGER_LIMIT: f64 = (1u64 << 52) as f64;
+        let factor = 10f64.powi(i32::try_from(decimals).unwrap_or(i32::MAX));
+        let mut geotransform = self.geotransform;
+        for value in geotransform.iter_mut() {
+            let scaled = *value * factor;
+            // also false for non-finite values
+            if scaled.abs() < INTEGER_LIMIT {
+                *value = scaled.round() / factor;
+            }
+        }
+        self.try_derive(geotransform)
+    }
+
+    /// Check if the pixel size terms `gt[1]` and `gt[5]` are both within `tolerance` of a
+    /// non-zero integer.
+    pub fn has_integer_resolution(&self, tolerance: f64) -> bool {
+        [1, 5].iter().all(|&i| {
+            let rounded = self.geotransform[i].round();
+            rounded != 0.0 && (self.geotransform[i] - rounded).abs() <= tolerance
+        })
+    }
+
+    /// Round the pixel size terms `gt[1]` and `gt[5]` to the nearest integer when they are
+    /// within `tolerance` of it, removing floating point noise like `29.999999999`.
+    ///
+    /// Origin and rotation terms are kept. Terms which would round to zero are kept as
+    /// well, and so is the whole geotransform in the unlikely case that rounding makes a
+    /// rotated geotransform non-invertible.
+    pub fn with_integer_resolution(&self, tolerance: f64) -> GeoTransformer {
+        let mut geotransform = self.geotransform;
+        for &i in [1, 5].iter() {
+            let rounded = geotransform[i].round();
+            if rounded != 0.0 && (geotransform[i] - rounded).abs() <= tolerance {
+                geotransform[i] = rounded;
+            }
+        }
+        self.try_derive(geotransform).unwrap_or_else(|_| self.clone())
+    }
+
+    /// Set the width and height of the pixels to `target`, keeping origin, orientation and
+    /// the sign of the pixel size terms.
+    ///
+    /// Both pixel axes are scaled to a length of `target`, so this also works for rotated
+    /// rasters. Note that the ground footprint of a dataset with the same number of pixels
+    /// changes unless the pixel counts are adjusted, which only works out exactly when the
+    /// extent is a multiple of `target`. Returns an error when `target` is zero or not finite.
+    pub fn snapped_resolution(&self, target: f64) -> Result<GeoTransformer, GeoTransformError> {
+        let resolution = self.resolution();
+        self.try_derive(self.scaled_pixel_axes((target.abs() / resolution.0, target.abs() / resolution.1)))
+    }
+
+    /// The transformer of the overview level with the resolution closest to `target`.
+    ///
+    /// Overview levels have a pixel size of a power of two multiple of the native pixel size.
+    /// Returns the factor of the level together with its transformer, a factor of 1 is
+    /// the native resolution. As with `snapped_resolution`, the footprint only stays the same
+    /// when the dataset size is a multiple of the factor.
+    pub fn overview_for_resolution(&self, target: f64) -> (u32, GeoTransformer) {
+        let exponent = (target.abs() / self.resolution().0).log2().round().clamp(0.0, 31.0) as u32;
+        let factor = 1u32 << exponent;
+        let scaled = self.scaled_pixel_axes((f64::from(factor), f64::from(factor)));
+        (factor, self.derive(scaled))
+    }
+
+    /// The geotransform with the column and row axes scaled by the given factors.
+    fn scaled_pixel_axes(&self, factors: (f64, f64)) -> GeoTransform {
+        let mut geotransform = self.geotransform;
+        geotransform[1] *= factors.0;
+        geotransform[4] *= factors.0;
+        geotransform[2] *= factors.1;
+        geotransform[5] *= factors.1;
+        geotransform
+    }
+
+    /// Serialize the geotransform to its six coefficients as little-endian `f64`s.
+    ///
+    /// The conventions are not included, `from_bytes` uses the default conventions.
+    pub fn to_bytes(&self) -> [u8; 48] {
+        let mut bytes = [0u8; 48];
+        for (chunk, value) in bytes.chunks_exact_mut(8).zip(self.geotransform.iter()) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserialize a transformer from the bytes created by `to_bytes`.
+    ///
+    /// The inverse geotransform is recomputed.
+    pub fn from_bytes(bytes: &[u8; 48]) -> Result<Self, GeoTransformError> {
+        let mut geotransform = GeoTransform::default();
+        for (value, chunk) in geotransform.iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut value_bytes = [0u8; 8];
+            value_bytes.copy_from_slice(chunk);
+            *value = f64::from_le_bytes(value_bytes);
+        }
+        GeoTransformer::try_from(geotransform)
+    }
+
+    /// The coefficients in the order of the `affine` package.
+    ///
+    /// `affine` orders the coefficients as `(a, b, c, d, e, f)` of the matrix
+    ///
+    /// ```text
+    /// | x |   | a b c |   | col |
+    /// | y | = | d e f | * | row |
+    /// | 1 |   | 0 0 1 |   |  1  |
+    /// ```
+    ///
+    /// while GDAL orders them as `[c, a, b, f, d, e]`, having the origin terms first. So
+    /// `(gt[1], gt[2], gt[0], gt[4], gt[5], gt[3])` is returned.
+    pub fn to_affine_tuple(&self) -> (f64, f64, f64, f64, f64, f64) {
+        let gt = &self.geotransform;
+        (gt[1], gt[2], gt[0], gt[4], gt[5], gt[3])
+    }
+
+    /// Create a transformer from coefficients in the order of the `affine` package.
+    ///
+    /// See `to_affine_tuple` for how the coefficients relate to GDALs ordering.
+    pub fn from_affine_tuple(affine: (f64, f64, f64, f64, f64, f64)) -> Result<Self, GeoTransformError> {
+        let (a, b, c, d, e, f) = affine;
+        GeoTransformer::try_from([c, a, b, f, d, e])
+    }
+
+    /// The matrix mapping geo-coordinates to normalized device coordinates of a dataset of the given size.
+    ///
+    /// The top-left corner of the dataset maps to `(-1, 1)` and the bottom-right corner to
+    /// `(1, -1)`, so the rows of the dataset run downwards in device space. The matrix is
+    /// row-major and is applied as `matrix * [x, y, 1]`, it needs to be transposed for
+    /// graphics APIs expecting column-major matrices. It is computed in `f64` before being
+    /// converted to `f32`.
+    pub fn to_ndc_matrix(&self, size: (usize, usize)) -> [[f32; 3]; 3] {
+        let inv = &self.inv_geotransform;
+        let scale_x = 2.0 / size.0 as f64;
+        let scale_y = -2.0 / size.1 as f64;
+        [
+            [(scale_x * inv[1]) as f32, (scale_x * inv[2]) as f32, (scale_x * inv[0] - 1.0) as f32],
+            [(scale_y * inv[4]) as f32, (scale_y * inv[5]) as f32, (scale_y * inv[3] + 1.0) as f32],
+            [0.0, 0.0, 1.0],
+        ]
+    }
+
+    /// The geotransform as the value of an SVG `transform` attribute mapping pixel coordinates
+    /// to SVG user space.
+    ///
+    /// SVG applies `matrix(a,b,c,d,e,f)` as `x' = a * x + c * y + e` and `y' = b * x + d * y + f`,
+    /// so the values are listed column by column, unlike the row by row ordering of GDAL. As the
+    /// y axis of SVG points down, the geographic y axis is flipped to keep north up, user space
+    /// being `(x, -y)` of the geo-coordinates. This results in
+    /// `matrix(gt[1],-gt[4],gt[2],-gt[5],gt[0],-gt[3])`.
+    pub fn to_svg_matrix(&self) -> String {
+        let gt = &self.geotransform;
+        // subtracting from zero avoids printing `-0` for zero terms
+        format!("matrix({},{},{},{},{},{})", gt[1], 0.0 - gt[4], gt[2], 0.0 - gt[5], gt[0], 0.0 - gt[3])
+    }
+
+    /// Wrap the transformer in an `Arc` to share it between threads.
+    pub fn shared(self) -> Arc<GeoTransformer> {
+        Arc::new(self)
+    }
+
+    /// The conventions used for conversions, see `with_conventions`.
+    pub fn conventions(&self) -> Conventions {
+        self.conventions
+    }
+
+    /// A copy of the transformer using the given conventions.
+    ///
+    /// Transformers derived from the returned one, e.g. using `for_window`, keep the
+    /// conventions. The geotransform is not modified.
+    pub fn with_conventions(&self, conventions: Conventions) -> GeoTransformer {
+        GeoTransformer { conventions, ..self.clone() }
+    }
+
+    /// Build a transformer from a geotransform derived from this one, keeping the conventions.
+    fn try_derive(&self, geotransform: GeoTransform) -> Result<GeoTransformer, GeoTransformError> {
+        GeoTransformer::try_from(geotransform).map(|derived| derived.with_conventions(self.conventions))
+    }
+
+    /// Same as `try_derive`.
+    ///
+    /// Only to be used when the linear part of the geotransform is known to be invertible.
+    fn derive(&self, geotransform: GeoTransform) -> GeoTransformer {
+        self.try_derive(geotransform).expect("derived geotransform must be invertible")
+    }
+}
+
+/// Transformers are compared by the bit patterns of their six geotransform coefficients
+/// and by their conventions.
+///
+/// This is bitwise equality, not approximate equality: coefficients differing in the
+/// last bit are not equal, while `NaN` values with the same bits are. Use `quantized`
+/// first when nearly identical transformers should compare equal.
+impl PartialEq for GeoTransformer {
+    fn eq(&self, other: &Self) -> bool {
+        self.conventions == other.conventions
+            && self.geotransform.iter().zip(other.geotransform.iter())
+                .all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+}
+
+impl Eq for GeoTransformer {}
+
+/// Hashes the bit patterns of the six geotransform coefficients and the conventions,
+/// consistent with `PartialEq`.
+impl Hash for GeoTransformer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for value in self.geotransform.iter() {
+            value.to_bits().hash(state);
+        }
+        self.conventions.hash(state);
+    }
+}
+
+/// The transformer and size of the mosaic covering all `tiles`.
+///
+/// All tiles must be grid-compatible with the first one according to `is_grid_compatible`,
+/// the mosaic shares their pixel size and its origin is the top-left corner of the union
+/// of the tiles in that grid.
+pub fn mosaic_transform(tiles: &[(&GeoTransformer, (usize, usize))]) -> Result<(GeoTransformer, (usize, usize)), GeoTransformError> {
+    let (reference, _) = tiles.first().ok_or(GeoTransformError::EmptyMosaic)?;
+
+    let mut min = (i64::MAX, i64::MAX);
+    let mut max = (i64::MIN, i64::MIN);
+    for (geotransformer, size) in tiles {
+        let offset = reference.grid_offset(geotransformer).ok_or(GeoTransformError::IncompatibleGrid)?;
+        min = (min.0.min(offset.0), min.1.min(offset.1));
+        max = (max.0.max(offset.0 + size.0 as i64), max.1.max(offset.1 + size.1 as i64));
+    }
+
+    let origin = reference.fractional_pixel_to_coordinate((min.0 as f64, min.1 as f64));
+    let mut geotransform = reference.geotransform;
+    geotransform[0] = origin.x;
+    geotransform[3] = origin.y;
+    let size = ((max.0 - min.0) as usize, (max.1 - min.1) as usize);
+    Ok((reference.derive(geotransform), size))
+}
+
+impl TryFrom<GeoTransform> for GeoTransformer {
+    type Error = GeoTransformError;
+
+    fn try_from(geotransform: GeoTransform) -> Result<Self, Self::Error> {
+        let inv_geotransform = invert_geotransform(&geotransform).ok_or(GeoTransformError::NotInvertible)?;
+        Ok(GeoTransformer { geotransform, inv_geotransform, conventions: Conventions::default() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+    use std::path::Path;
+
+    use gdal::raster::{Dataset, Driver};
+    use gdal::spatial_ref::SpatialRef;
+    use geo_types::{Coordinate, Point, Rect};
+
+    use crate::{Conventions, GeoTransformer, GeoTransformError, IntervalMode, mosaic_transform, PixelAnchor, Window};
+
+    macro_rules! assert_coordinates_relative_eq {
+        ($given:expr, $expected:expr) => {
+            assert_relative_eq!($given.x, $expected.x, epsilon = 0.000001);
+            assert_relative_eq!($given.y, $expected.y, epsilon = 0.000001);
+        }
+    }
+
+
+    fn open_dataset(dataset_filename: &str) -> (Dataset, GeoTransformer) {
+        let path = Path::new(dataset_filename);
+        let dataset = Dataset::open(path).unwrap();
+
+        let geotransform = dataset.geo_transform().unwrap();
+        let geotransformer = GeoTransformer::try_from(geotransform).unwrap();
+
+        (dataset, geotransformer)
+    }
+
+    #[test]
+    fn test_geotransformer_bounds() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let bounds = geotransformer.bounds_from_size(dataset.size());
+
+        // expected values where collected by using gdalinfo
+        assert_coordinates_relative_eq!(bounds.min(), Coordinate {
+            x: 11.3610659,
+            y: 32.2014463,
+        });
+        assert_coordinates_relative_eq!(bounds.max(), Coordinate {
+            x: 28.2838457,
+            y: 46.2520256,
+        });
+    }
+
+
+    #[test]
+    fn test_geotransformer_coordinate_to_pixel() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let bounds = geotransformer.bounds_from_size(dataset.size());
+
+        let c1 = geotransformer.coordinate_to_pixel(bounds.min());
+        assert_eq!(c1, (0, 44));
+        let c2 = geotransformer.coordinate_to_pixel(bounds.max());
+        assert_eq!(c2, (52, 0));
+    }
+
+    #[test]
+    fn test_geotransformer_for_block() {
+        let (_, geotransformer) = open_dataset("data/small.tiff");
+        let block_geotransformer = geotransformer.for_block((1, 2), (16, 8));
+
+        assert_coordinates_relative_eq!(
+            block_geotransformer.pixel_to_coordinate((3, 4)),
+            geotransformer.pixel_to_coordinate((19, 20))
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_flip_twice() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let (width, height) = dataset.size();
+
+        let flipped = geotransformer.flip_vertical(height).flip_vertical(height)
+            .flip_horizontal(width).flip_horizontal(width);
+        for i in 0..6 {
+            assert_relative_eq!(flipped.geotransform[i], geotransformer.geotransform[i], epsilon = 0.000001);
+            assert_relative_eq!(flipped.inv_geotransform[i], geotransformer.inv_geotransform[i], epsilon = 0.000001);
+        }
+    }
+
+    #[test]
+    fn test_geotransformer_flip_vertical() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let (width, height) = dataset.size();
+        let flipped = geotransformer.flip_vertical(height);
+
+        assert_coordinates_relative_eq!(
+            flipped.pixel_to_coordinate((0, 0)),
+            geotransformer.pixel_to_coordinate((0, height))
+        );
+        assert_coordinates_relative_eq!(
+            flipped.pixel_to_coordinate((width, height)),
+            geotransformer.pixel_to_coordinate((width, 0))
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_corners() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let bounds = geotransformer.bounds_from_size(dataset.size());
+        let corners = geotransformer.corners(dataset.size());
+
+        // the dataset is north-up, so the top-left corner is at min x and max y
+        assert_coordinates_relative_eq!(corners[0], Coordinate { x: bounds.min().x, y: bounds.max().y });
+        assert_coordinates_relative_eq!(corners[1], bounds.max());
+        assert_coordinates_relative_eq!(corners[2], Coordinate { x: bounds.max().x, y: bounds.min().y });
+        assert_coordinates_relative_eq!(corners[3], bounds.min());
+    }
+
+    #[test]
+    fn test_geotransformer_aligned_to() {
+        let reference = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let shifted = GeoTransformer::try_from([10.7, 0.5, 0.0, 49.6, 0.0, -0.5]).unwrap();
+
+        let aligned = shifted.aligned_to(&reference).unwrap();
+        assert_coordinates_relative_eq!(aligned.pixel_to_coordinate((0, 0)), Coordinate { x: 10.5, y: 49.5 });
+
+        let other_resolution = GeoTransformer::try_from([10.0, 0.25, 0.0, 50.0, 0.0, -0.25]).unwrap();
+        assert_eq!(other_resolution.aligned_to(&reference).err(), Some(GeoTransformError::IncompatiblePixelSize));
+    }
+
+    #[test]
+    fn test_geotransformer_bytes_roundtrip() {
+        let (_, geotransformer) = open_dataset("data/small.tiff");
+        let restored = GeoTransformer::from_bytes(&geotransformer.to_bytes()).unwrap();
+
+        assert_eq!(restored.geotransform, geotransformer.geotransform);
+        assert_eq!(restored.inv_geotransform, geotransformer.inv_geotransform);
+    }
+
+    #[test]
+    fn test_geotransformer_rect_pixel_span() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let bounds = geotransformer.bounds_from_size(dataset.size());
+        let (width, height) = geotransformer.rect_pixel_span(&bounds);
+
+        assert_relative_eq!(width, dataset.size().0 as f64, epsilon = 0.000001);
+        assert_relative_eq!(height, dataset.size().1 as f64, epsilon = 0.000001);
+    }
+
+    #[test]
+    fn test_geotransformer_affine_tuple() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.1, 50.0, 0.2, -0.5]).unwrap();
+        let affine = geotransformer.to_affine_tuple();
+        assert_eq!(affine, (0.5, 0.1, 10.0, 0.2, -0.5, 50.0));
+
+        let restored = GeoTransformer::from_affine_tuple(affine).unwrap();
+        assert_eq!(restored.geotransform, geotransformer.geotransform);
+    }
+
+    #[test]
+    fn test_geotransformer_as_map_key() {
+        let (_, geotransformer) = open_dataset("data/small.tiff");
+        let mut map = HashMap::new();
+        map.insert(geotransformer.clone(), "small");
+
+        assert_eq!(map.get(&geotransformer), Some(&"small"));
+        assert_eq!(map.get(&geotransformer.for_window((1, 0))), None);
+    }
+
+    #[test]
+    fn test_geotransformer_quantized() {
+        let a = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let b = GeoTransformer::try_from([10.0 + 1e-12, 0.5 - 1e-12, 0.0, 50.0, 0.0, -0.5 + 1e-12]).unwrap();
+        assert_ne!(a, b);
+        assert_eq!(a.quantized(9).unwrap(), b.quantized(9).unwrap());
+
+        // the pixel size rounds to zero
+        let fine = GeoTransformer::try_from([10.0, 0.25, 0.0, 50.0, 0.0, -0.25]).unwrap();
+        assert_eq!(fine.quantized(0).err(), Some(GeoTransformError::NotInvertible));
+
+        // scaling overflows or exceeds the precision of f64
+        let utm = GeoTransformer::try_from([500000.0, 30.0, 0.0, 4e6, 0.0, -30.0]).unwrap();
+        assert_eq!(utm.quantized(303).unwrap(), utm);
+        assert_eq!(utm.quantized(400).unwrap(), utm);
+        assert_eq!(utm.quantized(u32::MAX).unwrap(), utm);
+    }
+
+    #[test]
+    fn test_geotransformer_pixel_to_coordinate_anchored() {
+        let (_, geotransformer) = open_dataset("data/small.tiff");
+        let corner = geotransformer.pixel_to_coordinate_anchored((3, 4), PixelAnchor::Corner);
+        let center = geotransformer.pixel_to_coordinate_anchored((3, 4), PixelAnchor::Center);
+        let next_corner = geotransformer.pixel_to_coordinate((4, 5));
+
+        assert_coordinates_relative_eq!(corner, geotransformer.pixel_to_coordinate((3, 4)));
+        assert_coordinates_relative_eq!(center, Coordinate {
+            x: (corner.x + next_corner.x) / 2.0,
+            y: (corner.y + next_corner.y) / 2.0,
+        });
+    }
+
+    #[test]
+    fn test_geotransformer_fit_to_size() {
+        let rect = Rect::new(Coordinate { x: 10.0, y: 40.0 }, Coordinate { x: 20.0, y: 45.0 });
+        let geotransformer = GeoTransformer::fit_to_size(&rect, (200, 100)).unwrap();
+
+        assert_coordinates_relative_eq!(geotransformer.pixel_to_coordinate((0, 0)), Coordinate { x: 10.0, y: 45.0 });
+        assert_coordinates_relative_eq!(geotransformer.pixel_to_coordinate((200, 100)), Coordinate { x: 20.0, y: 40.0 });
+        assert_eq!(GeoTransformer::fit_to_size(&rect, (0, 100)).err(), Some(GeoTransformError::NotInvertible));
+    }
+
+    #[test]
+    fn test_geotransformer_footprint_polygon_densified() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+
+        let footprint = geotransformer.footprint_polygon(dataset.size());
+        assert_eq!(footprint.exterior().num_coords(), 5);
+
+        let densified = geotransformer.footprint_polygon_densified(dataset.size(), 21);
+        // 4 corners, 21 points per edge and the closing coordinate
+        assert_eq!(densified.exterior().num_coords(), 4 + 4 * 21 + 1);
+        assert_coordinates_relative_eq!(densified.exterior().0[22], geotransformer.corners(dataset.size())[1]);
+    }
+
+    #[test]
+    fn test_geotransformer_rect_to_window() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+
+        let rect = Rect::new(Coordinate { x: 10.7, y: 48.2 }, Coordinate { x: 12.0, y: 49.0 });
+        assert_eq!(geotransformer.rect_to_window(&rect, (100, 100)), Some(Window::new((1, 2), (3, 2))));
+
+        // clamped to the dataset
+        let rect = Rect::new(Coordinate { x: 5.0, y: 48.2 }, Coordinate { x: 12.0, y: 55.0 });
+        assert_eq!(geotransformer.rect_to_window(&rect, (3, 3)), Some(Window::new((0, 0), (3, 3))));
+
+        let rect = Rect::new(Coordinate { x: 0.0, y: 0.0 }, Coordinate { x: 1.0, y: 1.0 });
+        assert_eq!(geotransformer.rect_to_window(&rect, (100, 100)), None);
+    }
+
+    #[test]
+    fn test_geotransformer_row_and_column_linestring() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let (width, height) = dataset.size();
+
+        let row = geotransformer.row_linestring(3, width);
+        assert_eq!(row.num_coords(), width);
+        assert_coordinates_relative_eq!(row.0[5], geotransformer.pixel_to_coordinate_anchored((5, 3), PixelAnchor::Center));
+
+        let column = geotransformer.column_linestring(3, height);
+        assert_eq!(column.num_coords(), height);
+        assert_coordinates_relative_eq!(column.0[5], geotransformer.pixel_to_coordinate_anchored((3, 5), PixelAnchor::Center));
+    }
+
+    #[test]
+    fn test_geotransformer_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GeoTransformer>();
+    }
+
+    #[test]
+    fn test_geotransformer_invert_rotated() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.1, 50.0, 0.2, -0.5]).unwrap();
+        let coordinate = geotransformer.pixel_to_coordinate((7, 11));
+        assert_eq!(geotransformer.coordinate_to_pixel(Coordinate { x: coordinate.x + 0.01, y: coordinate.y - 0.01 }), (7, 11));
+
+        assert_eq!(GeoTransformer::try_from([10.0, 0.5, 1.0, 50.0, 0.25, 0.5]).err(), Some(GeoTransformError::NotInvertible));
+    }
+
+    #[test]
+    fn test_geotransformer_from_matrix_row_major() {
+        let from_matrix = GeoTransformer::from_matrix_row_major(0.5, 0.1, 10.0, 0.2, -0.5, 50.0).unwrap();
+        let from_gdal = GeoTransformer::from_gdal_order([10.0, 0.5, 0.1, 50.0, 0.2, -0.5]).unwrap();
+        assert_eq!(from_matrix, from_gdal);
+    }
+
+    #[test]
+    fn test_geotransformer_rotated_about() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let rotated = geotransformer.rotated_about(std::f64::consts::FRAC_PI_2, (10.0, 20.0)).unwrap();
+
+        // the pivot stays in place
+        assert_coordinates_relative_eq!(
+            rotated.fractional_pixel_to_coordinate((10.0, 20.0)),
+            geotransformer.fractional_pixel_to_coordinate((10.0, 20.0))
+        );
+        // the column axis now points north, the row axis east
+        let origin = rotated.pixel_to_coordinate((10, 20));
+        assert_coordinates_relative_eq!(rotated.pixel_to_coordinate((11, 20)), Coordinate { x: origin.x, y: origin.y + 0.5 });
+        assert_coordinates_relative_eq!(rotated.pixel_to_coordinate((10, 21)), Coordinate { x: origin.x + 0.5, y: origin.y });
+
+        assert_eq!(geotransformer.rotated_about(f64::NAN, (10.0, 20.0)).err(), Some(GeoTransformError::NotInvertible));
+        assert_eq!(geotransformer.rotated_about(f64::INFINITY, (10.0, 20.0)).err(), Some(GeoTransformError::NotInvertible));
+        assert_eq!(geotransformer.rotated_about(0.1, (f64::NAN, 20.0)).err(), Some(GeoTransformError::NotInvertible));
+    }
+
+    #[test]
+    fn test_geotransformer_enclosing_window() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let coords = [
+            Coordinate { x: 10.7, y: 48.2 },
+            Coordinate { x: 12.0, y: 49.9 },
+            Coordinate { x: f64::NAN, y: 49.0 },
+        ];
+        assert_eq!(geotransformer.enclosing_window(&coords, (100, 100)), Some(Window::new((1, 0), (4, 4))));
+        assert_eq!(geotransformer.enclosing_window(&coords, (3, 100)), Some(Window::new((1, 0), (2, 4))));
+        assert_eq!(geotransformer.enclosing_window(&[Coordinate { x: 0.0, y: 0.0 }], (100, 100)), None);
+        assert_eq!(geotransformer.enclosing_window(&[], (100, 100)), None);
+    }
+
+    #[test]
+    fn test_geotransformer_grid_compatibility() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+
+        let shifted = GeoTransformer::try_from([8.5, 0.5, 0.0, 49.0, 0.0, -0.5]).unwrap();
+        assert!(geotransformer.is_grid_compatible(&shifted, 1e-6));
+        assert_eq!(geotransformer.grid_offset(&shifted), Some((-3, 2)));
+
+        let half_pixel = GeoTransformer::try_from([10.25, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        assert!(!geotransformer.is_grid_compatible(&half_pixel, 1e-6));
+        assert_eq!(geotransformer.grid_offset(&half_pixel), None);
+
+        let other_resolution = GeoTransformer::try_from([10.0, 0.25, 0.0, 50.0, 0.0, -0.25]).unwrap();
+        assert!(!geotransformer.is_grid_compatible(&other_resolution, 1e-6));
+    }
+
+    #[test]
+    fn test_geotransformer_synthetic() {
+        let envelope = Rect::new(Coordinate { x: 10.0, y: 40.0 }, Coordinate { x: 20.0, y: 45.2 });
+        let (geotransformer, size) = GeoTransformer::synthetic(&envelope, 0.5).unwrap();
+
+        assert_eq!(size, (20, 11));
+        assert_coordinates_relative_eq!(geotransformer.pixel_to_coordinate((0, 0)), Coordinate { x: 10.0, y: 45.2 });
+        assert_eq!(GeoTransformer::synthetic(&envelope, 0.0).err(), Some(GeoTransformError::NotInvertible));
+    }
+
+    #[test]
+    fn test_geotransformer_coordinate_at_fraction() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let corners = geotransformer.corners(dataset.size());
+
+        assert_coordinates_relative_eq!(geotransformer.coordinate_at_fraction(0.0, 0.0, dataset.size()), corners[0]);
+        assert_coordinates_relative_eq!(geotransformer.coordinate_at_fraction(1.0, 1.0, dataset.size()), corners[2]);
+        assert_coordinates_relative_eq!(
+            geotransformer.coordinate_at_fraction(0.5, 0.5, dataset.size()),
+            geotransformer.bounds_from_size(dataset.size()).center()
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_summary() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.25]).unwrap();
+        let summary = geotransformer.summary((20, 10));
+
+        assert_eq!(summary.resolution, (0.5, 0.25));
+        assert_eq!(summary.pixel_count, 200);
+        assert_relative_eq!(summary.pixel_area, 0.125, epsilon = 0.000001);
+        assert_relative_eq!(summary.total_area, summary.bounds.width() * summary.bounds.height(), epsilon = 0.000001);
+        assert!(!summary.has_rotation);
+        assert!(geotransformer.rotated_about(0.1, (0.0, 0.0)).unwrap().has_rotation());
+    }
+
+    #[test]
+    fn test_geotransformer_coordinate_to_block_index() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+
+        assert_eq!(geotransformer.coordinate_to_block_index(Coordinate { x: 18.1, y: 49.9 }, (16, 8)), (1, 0));
+        // one pixel left of and above the origin
+        assert_eq!(geotransformer.coordinate_to_pixel_signed(Coordinate { x: 9.9, y: 50.1 }), (-1, -1));
+        assert_eq!(geotransformer.coordinate_to_block_index(Coordinate { x: 9.9, y: 50.1 }, (16, 8)), (-1, -1));
+    }
+
+    #[test]
+    fn test_geotransformer_coordinates_to_pixels_checked() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let bounds = geotransformer.bounds_from_size(dataset.size());
+        let coords = [
+            geotransformer.pixel_to_coordinate_anchored((4, 7), PixelAnchor::Center),
+            Coordinate { x: bounds.max().x + 1.0, y: bounds.center().y },
+            Coordinate { x: f64::NAN, y: bounds.center().y },
+        ];
+
+        assert_eq!(geotransformer.coordinates_to_pixels_checked(&coords, dataset.size()), vec![Some((4, 7)), None, None]);
+    }
+
+    #[test]
+    fn test_geotransformer_to_ndc_matrix() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let matrix = geotransformer.to_ndc_matrix(dataset.size());
+        let apply = |c: Coordinate<f64>| {
+            let (x, y) = (c.x as f32, c.y as f32);
+            Coordinate {
+                x: matrix[0][0] * x + matrix[0][1] * y + matrix[0][2],
+                y: matrix[1][0] * x + matrix[1][1] * y + matrix[1][2],
+            }
+        };
+        let corners = geotransformer.corners(dataset.size());
+
+        assert_relative_eq!(apply(corners[0]).x, -1.0, epsilon = 0.0001);
+        assert_relative_eq!(apply(corners[0]).y, 1.0, epsilon = 0.0001);
+        assert_relative_eq!(apply(corners[2]).x, 1.0, epsilon = 0.0001);
+        assert_relative_eq!(apply(corners[2]).y, -1.0, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_geotransformer_envelope_of_pixel_rect_rotated() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap()
+            .rotated_about(std::f64::consts::FRAC_PI_4, (0.0, 0.0)).unwrap();
+        let envelope = geotransformer.envelope_of_pixel_rect((0.0, 0.0), (2.0, 2.0));
+
+        // the diagonal of the rotated square is 2 * sqrt(2) * 0.5
+        assert_relative_eq!(envelope.width(), 2f64.sqrt(), epsilon = 0.000001);
+        assert_relative_eq!(envelope.height(), 2f64.sqrt(), epsilon = 0.000001);
+    }
+
+    #[test]
+    fn test_geotransformer_coordinate_to_pixel_as() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+
+        assert_eq!(geotransformer.coordinate_to_pixel_as::<u32>(Coordinate { x: 11.2, y: 49.9 }), Ok((2, 0)));
+        assert_eq!(geotransformer.coordinate_to_pixel_as::<i32>(Coordinate { x: 9.9, y: 49.9 }), Ok((-1, 0)));
+        assert_eq!(
+            geotransformer.coordinate_to_pixel_as::<u32>(Coordinate { x: 9.9, y: 49.9 }),
+            Err(GeoTransformError::PixelOutOfRange)
+        );
+        assert_eq!(
+            geotransformer.coordinate_to_pixel_as::<u8>(Coordinate { x: 1000.0, y: 49.9 }),
+            Err(GeoTransformError::PixelOutOfRange)
+        );
+        assert_eq!(
+            geotransformer.coordinate_to_pixel_as::<i64>(Coordinate { x: f64::NAN, y: 49.9 }),
+            Err(GeoTransformError::PixelOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_subpixel_offset() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let other = GeoTransformer::try_from([11.1, 0.5, 0.0, 49.3, 0.0, -0.5]).unwrap();
+        let (x, y) = geotransformer.subpixel_offset(&other);
+
+        assert_relative_eq!(x, 0.2, epsilon = 0.000001);
+        assert_relative_eq!(y, 0.4, epsilon = 0.000001);
+    }
+
+    #[test]
+    fn test_geotransformer_with_border() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let bordered = geotransformer.with_border(3);
+        let bordered_size = geotransformer.size_with_border(dataset.size(), 3);
+
+        assert_eq!(bordered_size, (dataset.size().0 + 6, dataset.size().1 + 6));
+        assert_coordinates_relative_eq!(bordered.pixel_to_coordinate((3, 3)), geotransformer.pixel_to_coordinate((0, 0)));
+        assert_coordinates_relative_eq!(
+            bordered.coordinate_at_fraction(0.5, 0.5, bordered_size),
+            geotransformer.coordinate_at_fraction(0.5, 0.5, dataset.size())
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_window_ground_size_rotated() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap()
+            .rotated_about(std::f64::consts::FRAC_PI_4, (0.0, 0.0)).unwrap();
+        let window = Window::new((0, 0), (2, 4));
+
+        let (width, height) = geotransformer.window_ground_size(&window);
+        assert_relative_eq!(width, 1.0, epsilon = 0.000001);
+        assert_relative_eq!(height, 2.0, epsilon = 0.000001);
+
+        // the envelope of the rotated window is larger
+        assert!(geotransformer.window_to_rect(&window).width() > width);
+    }
+
+    #[test]
+    fn test_geotransformer_kernel_fits() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let at_pixel = |col: usize, row: usize| geotransformer.pixel_to_coordinate_anchored((col, row), PixelAnchor::Center);
+
+        assert!(geotransformer.kernel_fits(at_pixel(1, 1), 1, (10, 10)));
+        assert!(!geotransformer.kernel_fits(at_pixel(0, 1), 1, (10, 10)));
+        assert!(geotransformer.kernel_fits(at_pixel(8, 8), 1, (10, 10)));
+        assert!(!geotransformer.kernel_fits(at_pixel(9, 8), 1, (10, 10)));
+
+        assert!(!geotransformer.kernel_fits(Coordinate { x: f64::NAN, y: f64::NAN }, 0, (10, 10)));
+        assert!(!geotransformer.kernel_fits(Coordinate { x: 1e300, y: -1e300 }, 1, (10, 10)));
+        assert!(!geotransformer.kernel_fits(Coordinate { x: -1e300, y: 1e300 }, 1, (10, 10)));
+        assert!(!geotransformer.kernel_fits(at_pixel(5, 5), usize::MAX, (10, 10)));
+    }
+
+    #[test]
+    fn test_geotransformer_coordinate_to_pixel_snapped() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        let max = geotransformer.bounds_from_size(dataset.size()).max();
+
+        // the max corner ends up just left of the pixel boundary
+        assert_eq!(geotransformer.coordinate_to_pixel_signed(max), (52, 0));
+        assert_eq!(geotransformer.coordinate_to_pixel_snapped(max, 1e-6), (53, 0));
+    }
+
+    #[test]
+    fn test_geotransformer_diagonal_ground_length_rotated() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap()
+            .rotated_about(std::f64::consts::FRAC_PI_3, (0.0, 0.0)).unwrap();
+        let size = (30, 40);
+
+        let diagonal = geotransformer.diagonal_ground_length(size);
+        assert_relative_eq!(diagonal, 25.0, epsilon = 0.000001);
+
+        let gt = geotransformer.geotransform;
+        let naive = (size.0 as f64 * gt[1]).hypot(size.1 as f64 * gt[5]);
+        assert!((naive - diagonal).abs() > 1.0);
+    }
+
+    #[test]
+    fn test_geotransformer_boundary_coordinates() {
+        let geotransformer = GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+        let pixels: Vec<_> = geotransformer.boundary_coordinates((3, 4), 1)
+            .map(|c| geotransformer.coordinate_to_pixel(c))
+            .collect();
+
+        assert_eq!(pixels, vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3), (1, 3), (0, 3), (0, 2), (0, 1)]);
+        assert_eq!(geotransformer.boundary_coordinates((3, 4), 3).count(), 4);
+        assert_eq!(geotransformer.boundary_coordinates((3, 1), 1).count(), 3);
+    }
+
+    #[test]
+    fn test_mosaic_transform() {
+        let left = GeoTransformer::try_from([10.0, 0.5, 0.0, 20.0, 0.0, -0.5]).unwrap();
+        let right = GeoTransformer::try_from([15.0, 0.5, 0.0, 21.0, 0.0, -0.5]).unwrap();
+
+        let (mosaic, size) = mosaic_transform(&[(&left, (10, 8)), (&right, (6, 4))]).unwrap();
+        assert_eq!(mosaic, GeoTransformer::try_from([10.0, 0.5, 0.0, 21.0, 0.0, -0.5]).unwrap());
+        assert_eq!(size, (16, 10));
+
+        let shifted = GeoTransformer::try_from([15.25, 0.5, 0.0, 21.0, 0.0, -0.5]).unwrap();
+        assert_eq!(mosaic_transform(&[(&left, (10, 8)), (&shifted, (6, 4))]).err(), Some(GeoTransformError::IncompatibleGrid));
+        assert_eq!(mosaic_transform(&[]).err(), Some(GeoTransformError::EmptyMosaic));
+    }
+
+    #[test]
+    fn test_geotransformer_pixels_along_line() {
+        let geotransformer = GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+
+        let pixels: Vec<_> = geotransformer.pixels_along_line(Coordinate { x: 0.5, y: -0.5 }, Coordinate { x: 4.5, y: -2.5 }).collect();
+        assert_eq!(pixels, vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+
+        let pixels: Vec<_> = geotransformer.pixels_along_line(Coordinate { x: 1.5, y: 0.5 }, Coordinate { x: -1.5, y: 0.5 }).collect();
+        assert_eq!(pixels, vec![(1, -1), (0, -1), (-1, -1), (-2, -1)]);
+
+        let single = Coordinate { x: 2.5, y: -2.5 };
+        assert_eq!(geotransformer.pixels_along_line(single, single).collect::<Vec<_>>(), vec![(2, 2)]);
+
+        let not_finite = Coordinate { x: f64::NAN, y: -2.5 };
+        assert_eq!(geotransformer.pixels_along_line(single, not_finite).count(), 0);
+        let far_away = Coordinate { x: 1e300, y: -2.5 };
+        assert_eq!(geotransformer.pixels_along_line(far_away, single).count(), 0);
+        let infinite = Coordinate { x: 0.5, y: f64::NEG_INFINITY };
+        assert_eq!(geotransformer.pixels_along_line(single, infinite).count(), 0);
+    }
+
+    #[test]
+    fn test_geotransformer_from_dataset_strict() {
+        let (dataset, geotransformer) = open_dataset("data/small.tiff");
+        assert_eq!(GeoTransformer::from_dataset_strict(&dataset).unwrap(), geotransformer);
+    }
+
+    #[test]
+    fn test_geotransformer_from_dataset_strict_ungeoreferenced() {
+        let driver = Driver::get("MEM").unwrap();
+
+        let dataset = driver.create("", 4, 4, 1).unwrap();
+        assert_eq!(GeoTransformer::from_dataset_strict(&dataset).err(), Some(GeoTransformError::MissingGeoTransform));
+
+        dataset.set_geo_transform(&[0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).unwrap();
+        assert_eq!(GeoTransformer::from_dataset_strict(&dataset).err(), Some(GeoTransformError::LooksUngeoreferenced));
+        assert!(GeoTransformer::from_dataset(&dataset).is_ok());
+    }
+
+    #[test]
+    fn test_geotransformer_apply_generic() {
+        let geotransformer = GeoTransformer::try_from([10.0, 2.0, 0.0, 20.0, 0.0, -2.0]).unwrap();
+
+        let coordinate: Coordinate<f64> = geotransformer.apply_pixel_to_coord((1.5, 2.0));
+        assert_coordinates_relative_eq!(coordinate, Coordinate { x: 13.0, y: 16.0 });
+        let point: Point<f64> = geotransformer.apply_pixel_to_coord((1.5, 2.0));
+        assert_eq!(point.0, coordinate);
+
+        let pixel: (f64, f64) = geotransformer.apply_coord_to_pixel((13.0, 16.0));
+        assert_relative_eq!(pixel.0, 1.5, epsilon = 0.000001);
+        assert_relative_eq!(pixel.1, 2.0, epsilon = 0.000001);
+    }
+
+    #[test]
+    fn test_geotransformer_from_subdataset_out_of_range() {
+        assert_eq!(
+            GeoTransformer::from_subdataset("data/small.tiff", 1).err(),
+            Some(GeoTransformError::NoSuchSubdataset(1))
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_snapped_resolution() {
+        let geotransformer = GeoTransformer::try_from([10.0, 2.0, 0.0, 20.0, 0.0, -2.0]).unwrap();
+
+        assert_eq!(geotransformer.snapped_resolution(5.0).unwrap(), GeoTransformer::try_from([10.0, 5.0, 0.0, 20.0, 0.0, -5.0]).unwrap());
+        assert_eq!(geotransformer.snapped_resolution(0.0).err(), Some(GeoTransformError::NotInvertible));
+
+        let (factor, overview) = geotransformer.overview_for_resolution(7.0);
+        assert_eq!(factor, 4);
+        assert_eq!(overview, GeoTransformer::try_from([10.0, 8.0, 0.0, 20.0, 0.0, -8.0]).unwrap());
+        assert_eq!(geotransformer.overview_for_resolution(1.0).0, 1);
+    }
+
+    #[test]
+    fn test_geotransformer_from_iter_checked() {
+        let coefficients = [10.0, 2.0, 0.0, 20.0, 0.0, -2.0];
+
+        assert_eq!(
+            GeoTransformer::from_iter_checked(coefficients.iter().cloned()).unwrap(),
+            GeoTransformer::try_from([10.0, 2.0, 0.0, 20.0, 0.0, -2.0]).unwrap()
+        );
+        assert_eq!(
+            GeoTransformer::from_iter_checked(coefficients[..5].iter().cloned()).err(),
+            Some(GeoTransformError::WrongCoefficientCount { found: 5 })
+        );
+        assert_eq!(
+            GeoTransformer::from_iter_checked(coefficients.iter().cloned().chain(Some(1.0))).err(),
+            Some(GeoTransformError::WrongCoefficientCount { found: 7 })
+        );
+    }
+
+    #[test]
+    fn test_geotransformer_conventions() {
+        let gdal = GeoTransformer::try_from([10.0, 1.0, 0.0, 20.0, 0.0, -1.0]).unwrap();
+        let conventions = Conventions { anchor: PixelAnchor::Center, interval: IntervalMode::Closed };
+        let configured = gdal.with_conventions(conventions);
+        assert_eq!(gdal.conventions(), Conventions::default());
+        assert_ne!(gdal, configured);
+
+        assert_coordinates_relative_eq!(configured.pixel_to_coordinate((2, 3)), Coordinate { x: 12.5, y: 16.5 });
+        assert_eq!(configured.coordinate_to_pixel(configured.pixel_to_coordinate((2, 3))), (2, 3));
+
+        // the bottom-right corner of the dataset
+        let corner = Coordinate { x: 14.0, y: 16.0 };
+        assert_eq!(gdal.coordinate_to_pixel_checked(corner, (4, 4)), None);
+        assert_eq!(configured.coordinate_to_pixel_checked(corner, (4, 4)), Some((3, 3)));
+        assert!(!gdal.kernel_fits(corner, 0, (4, 4)));
+        assert!(configured.kernel_fits(corner, 0, (4, 4)));
+
+        let rect = Rect::new(Coordinate { x: 11.0, y: 19.0 }, Coordinate { x: 12.0, y: 18.0 });
+        assert_eq!(gdal.rect_to_window(&rect, (4, 4)), Some(Window::new((1, 1), (1, 1))));
+        assert_eq!(configured.rect_to_window(&rect, (4, 4)), Some(Window::new((0, 0), (3, 3))));
+        let inner = Rect::new(Coordinate { x: 11.5, y: 18.5 }, Coordinate { x: 12.5, y: 17.5 });
+        assert_eq!(configured.rect_to_window(&inner, (4, 4)), gdal.rect_to_window(&inner, (4, 4)));
+
+        // derived transformers keep the conventions, the grid is unchanged
+        let window = configured.for_window((1, 1));
+        assert_eq!(window.conventions(), conventions);
+        assert_eq!(window, gdal.for_window((1, 1)).with_conventions(conventions));
+        assert_eq!(configured.bounds_from_size((4, 4)), gdal.bounds_from_size((4, 4)));
+    }
+
+    #[test]
+    fn test_geotransformer_to_svg_matrix() {
+        let geotransformer = GeoTransformer::try_from([10.0, 2.0, 0.0, 20.0, 0.0, -2.5]).unwrap();
+        assert_eq!(geotransformer.to_svg_matrix(), "matrix(2,0,0,2.5,10,-20)");
+
+        let rotated = GeoTransformer::try_from([10.0, 2.0, 0.5, 20.0, 0.25, -2.5]).unwrap();
+        assert_eq!(rotated.to_svg_matrix(), "matrix(2,-0.25,0.5,2.5,10,-20)");
+    }
+
+    #[test]
+    fn test_geotransformer_transform_coords_in_place() {
+        let geotransformer = GeoTransformer::try_from([10.0, 2.0, 0.0, 20.0, 0.0, -2.0]).unwrap();
+        let mut xs = [10.0, 13.0, 30.0];
+        let mut ys = [20.0, 16.0, 0.0];
+
+        geotransformer.transform_coords_in_place(&mut xs, &mut ys);
+        for (i, expected) in [(0.0, 0.0), (1.5, 2.0), (10.0, 10.0)].iter().enumerate() {
+            assert_relative_eq!(xs[i], expected.0, epsilon = 0.000001);
+            assert_relative_eq!(ys[i], expected.1, epsilon = 0.000001);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geotransformer_transform_coords_in_place_mismatched_lengths() {
+        let geotransformer = GeoTransformer::try_from([10.0, 2.0, 0.0, 20.0, 0.0, -2.0]).unwrap();
+        geotransformer.transform_coords_in_place(&mut [1.0, 2.0], &mut [1.0]);
+    }
+
+    #[test]
+    fn test_geotransformer_integer_resolution() {
+        let geotransformer = GeoTransformer::try_from([500000.0, 29.999999999, 0.0, 4000000.0, 0.0, -30.000000001]).unwrap();
+        assert!(!geotransformer.has_integer_resolution(1e-12));
+        assert!(geotransformer.has_integer_resolution(1e-6));
+
+        let snapped = geotransformer.with_integer_resolution(1e-6);
+        assert_eq!(snapped, GeoTransformer::try_from([500000.0, 30.0, 0.0, 4000000.0, 0.0, -30.0]).unwrap());
+        assert_eq!(geotransformer.with_integer_resolution(1e-12), geotransformer);
+
+        let fractional = GeoTransformer::try_from([0.0, 0.25, 0.0, 0.0, 0.0, -0.25]).unwrap();
+        assert!(!fractional.has_integer_resolution(0.5));
+        assert_eq!(fractional.with_integer_resolution(0.5), fractional);
+    }
+
+    #[test]
+    fn test_geotransformer_suggest_warp_output() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap();
+        let lonlat = SpatialRef::from_proj4("+proj=longlat +datum=WGS84 +no_defs").unwrap();
+
+        let (output, size) = geotransformer.suggest_warp_output((20, 10), &lonlat, &lonlat).unwrap();
+        assert_eq!(size, (20, 10));
+        assert_coordinates_relative_eq!(output.pixel_to_coordinate((0, 0)), Coordinate { x: 10.0, y: 50.0 });
+        assert_relative_eq!(output.resolution().0, 0.5, epsilon = 0.000001);
+
+        let mercator = SpatialRef::from_epsg(3857).unwrap();
+        let (output, size) = geotransformer.suggest_warp_output((20, 10), &lonlat, &mercator).unwrap();
+        let bounds = output.bounds_from_size(size);
+        assert!(bounds.min().x > 1_000_000.0 && bounds.max().x < 2_300_000.0);
+        assert!(!output.has_rotation());
+
+        let closed = geotransformer.with_conventions(Conventions { anchor: PixelAnchor::Center, interval: IntervalMode::Closed });
+        let (output, _) = closed.suggest_warp_output((20, 10), &lonlat, &lonlat).unwrap();
+        assert_eq!(output.conventions(), closed.conventions());
+
+        assert_eq!(geotransformer.suggest_warp_output((0, 0), &lonlat, &lonlat).err(), Some(GeoTransformError::NotInvertible));
+    }
+
+    #[test]
+    fn test_geotransformer_identity_const() {
+        const IDENTITY: GeoTransformer = GeoTransformer::identity();
+
+        assert_eq!(IDENTITY, GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).unwrap());
+        assert_eq!(IDENTITY.inv_geotransform, GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, 1.0]).unwrap().inv_geotransform);
+        assert_eq!(IDENTITY.coordinate_to_pixel(Coordinate { x: 3.5, y: 7.25 }), (3, 7));
+    }
+
+    #[test]
+    fn test_geotransformer_contains_pixel() {
+        let geotransformer = GeoTransformer::try_from([10.0, 1.0, 0.0, 20.0, 0.0, -1.0]).unwrap();
+        let size = (4, 3);
+
+        assert!(geotransformer.contains_pixel((0, 0), size));
+        assert!(geotransformer.contains_pixel((3, 2), size));
+        assert!(!geotransformer.contains_pixel((-1, 0), size));
+        assert!(!geotransformer.contains_pixel((0, -1), size));
+        assert!(!geotransformer.contains_pixel((4, 0), size));
+        assert!(!geotransformer.contains_pixel((0, 3), size));
+
+        assert_eq!(
+            geotransformer.pixel_bounds(size),
+            Rect::new(Coordinate { x: 0.0, y: 0.0 }, Coordinate { x: 4.0, y: 3.0 })
+        );
+    }
+
+    #[cfg(feature = "proj")]
+    #[test]
+    fn test_geotransformer_reproject_bounds_proj() {
+        let geotransformer = GeoTransformer::try_from([10.0, 1.0, 0.0, 50.0, 0.0, -1.0]).unwrap();
+        let proj = proj::Proj::new_known_crs("EPSG:4326", "EPSG:3857", None).unwrap();
+
+        let bounds = geotransformer.reproject_bounds_proj((10, 10), &proj).unwrap();
+        assert_relative_eq!(bounds.min().x, 1113194.9079, epsilon = 0.01);
+        assert_relative_eq!(bounds.max().x, 2226389.8159, epsilon = 0.01);
+        assert_relative_eq!(bounds.min().y, 4865942.2795, epsilon = 0.01);
+        assert_relative_eq!(bounds.max().y, 6446275.8410, epsilon = 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geotransformer_coordinate_to_block_index_zero_block_size() {
+        let geotransformer = GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+        geotransformer.coordinate_to_block_index(Coordinate { x: 1.0, y: -1.0 }, (0, 256));
+    }
+
+    #[test]
+    fn test_geotransformer_approximate_from_rpc_bbox() {
+        let extent = Rect::new(Coordinate { x: 12.0, y: 45.0 }, Coordinate { x: 12.5, y: 45.2 });
+        let geotransformer = GeoTransformer::approximate_from_rpc_bbox(&extent, (1000, 400)).unwrap();
+
+        assert_coordinates_relative_eq!(geotransformer.pixel_to_coordinate((0, 0)), Coordinate { x: 12.0, y: 45.2 });
+        assert_coordinates_relative_eq!(geotransformer.pixel_to_coordinate((1000, 400)), Coordinate { x: 12.5, y: 45.0 });
+        assert!(GeoTransformer::approximate_from_rpc_bbox(&extent, (0, 400)).is_err());
+    }
+
+    #[test]
+    fn test_geotransformer_point_to_pixel() {
+        let geotransformer = GeoTransformer::try_from([100.0, 10.0, 0.0, 200.0, 0.0, -10.0]).unwrap();
+
+        let point = geotransformer.pixel_to_point((3, 4));
+        assert_coordinates_relative_eq!(point.0, Coordinate { x: 130.0, y: 160.0 });
+        assert_eq!(geotransformer.point_to_pixel(point), (3, 4));
+
+        // above and left of the origin
+        assert_eq!(geotransformer.point_to_pixel(Point::new(95.0, 205.0)), (-1, -1));
+        assert_eq!(geotransformer.point_to_pixel(Point::new(75.0, 150.0)), (-3, 5));
     }
 }
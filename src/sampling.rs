@@ -0,0 +1,234 @@
+use gdal::errors::Result;
+use gdal::metadata::Metadata;
+use gdal::raster::{Dataset, RasterBand};
+use geo_types::{Coordinate, Rect};
+
+use crate::{GeoTransformer, Window};
+
+/// How the values of a raster relate to its pixel grid.
+///
+/// Corresponds to the `AREA_OR_POINT` metadata item of GDAL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelInterpretation {
+    /// The value represents the whole area of the pixel, so it is located at the center
+    /// of the pixel. Bilinear interpolation uses the four pixel centers surrounding the
+    /// coordinate. This is the default of GDAL.
+    ///
+    /// This is also correct for `PixelIsPoint` GeoTIFFs opened with the default settings of
+    /// GDAL, which shifts their geotransform by half a pixel to refer to the pixel corners.
+    #[default]
+    Area,
+    /// The value is located at the pixel coordinate of the geotransform itself.
+    ///
+    /// Only use this when the geotransform points at the sample locations instead of the
+    /// pixel corners, e.g. for `PixelIsPoint` GeoTIFFs opened with `GTIFF_POINT_GEO_IGNORE`
+    /// set to `TRUE`. The neighborhood is then shifted by half a pixel compared to `Area`.
+    Point,
+}
+
+impl PixelInterpretation {
+    /// Read the interpretation from the `AREA_OR_POINT` metadata item of the dataset.
+    ///
+    /// Falls back to `Area` when the item is missing. See `PixelInterpretation::Point` for
+    /// when the `Point` interpretation reported by GDAL applies to the geotransform.
+    pub fn from_dataset(dataset: &Dataset) -> PixelInterpretation {
+        match dataset.metadata_item("AREA_OR_POINT", "") {
+            Some(ref value) if value.eq_ignore_ascii_case("point") => PixelInterpretation::Point,
+            _ => PixelInterpretation::Area,
+        }
+    }
+}
+
+/// Check if the value is the nodata value of a band.
+///
+/// A `NaN` nodata value, common for float rasters, matches all `NaN` values.
+fn is_no_data(no_data: Option<f64>, value: f64) -> bool {
+    match no_data {
+        Some(no_data) if no_data.is_nan() => value.is_nan(),
+        Some(no_data) => no_data == value,
+        None => false,
+    }
+}
+
+/// Interpolate between the values `[top_left, top_right, bottom_left, bottom_right]`
+/// using the weights of the right column and the bottom row.
+fn interpolate_bilinear(values: [f64; 4], weights: (f64, f64)) -> f64 {
+    let top = values[0] * (1.0 - weights.0) + values[1] * weights.0;
+    let bottom = values[2] * (1.0 - weights.0) + values[3] * weights.0;
+    top * (1.0 - weights.1) + bottom * weights.1
+}
+
+impl GeoTransformer {
+    /// Sample the value of the pixel of the band containing the coordinate.
+    ///
+    /// Returns `None` when the coordinate is outside of the band or the pixel is nodata.
+    pub fn sample_nearest(&self, band: &RasterBand, coordinate: Coordinate<f64>) -> Result<Option<f64>> {
+        let pixel = match self.coordinate_to_pixel_checked(coordinate, band.size()) {
+            Some(pixel) => pixel,
+            None => return Ok(None),
+        };
+        let buffer = band.read_as::<f64>((pixel.0 as isize, pixel.1 as isize), (1, 1), (1, 1))?;
+        let value = buffer.data[0];
+        if is_no_data(band.no_data_value(), value) {
+            Ok(None)
+        } else {
+            Ok(Some(value))
+        }
+    }
+
+    /// The 2×2 neighborhood of pixels used for bilinear interpolation at the coordinate.
+    ///
+    /// Returns the top-left pixel of the neighborhood and the weights of its right column
+    /// and bottom row. With `PixelInterpretation::Area` the values are located at the pixel
+    /// centers, so the neighborhood is shifted by half a pixel compared to
+    /// `PixelInterpretation::Point`.
+    pub fn bilinear_neighborhood(&self, coordinate: Coordinate<f64>, interpretation: PixelInterpretation) -> ((i64, i64), (f64, f64)) {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        let (x, y) = match interpretation {
+            PixelInterpretation::Area => (pixel.0 - 0.5, pixel.1 - 0.5),
+            PixelInterpretation::Point => pixel,
+        };
+        let (x0, y0) = (x.floor(), y.floor());
+        ((x0 as i64, y0 as i64), (x - x0, y - y0))
+    }
+
+    /// Sample the band at the coordinate using bilinear interpolation.
+    ///
+    /// Returns `None` when the coordinate is not finite, or the 2×2 neighborhood is not
+    /// completely within the band or contains a nodata value.
+    pub fn sample_bilinear(&self, band: &RasterBand, coordinate: Coordinate<f64>, interpretation: PixelInterpretation) -> Result<Option<f64>> {
+        let pixel = self.coordinate_to_fractional_pixel(coordinate);
+        if !(pixel.0.is_finite() && pixel.1.is_finite()) {
+            return Ok(None);
+        }
+        let (origin, weights) = self.bilinear_neighborhood(coordinate, interpretation);
+        let size = band.size();
+        let opposite = match (origin.0.checked_add(1), origin.1.checked_add(1)) {
+            (Some(col), Some(row)) => (col, row),
+            _ => return Ok(None),
+        };
+        if !(self.contains_pixel(origin, size) && self.contains_pixel(opposite, size)) {
+            return Ok(None);
+        }
+
+        let buffer = band.read_as::<f64>((origin.0 as isize, origin.1 as isize), (2, 2), (2, 2))?;
+        let no_data = band.no_data_value();
+        if buffer.data.iter().any(|value| is_no_data(no_data, *value)) {
+            return Ok(None);
+        }
+        let values = [buffer.data[0], buffer.data[1], buffer.data[2], buffer.data[3]];
+        Ok(Some(interpolate_bilinear(values, weights)))
+    }
+
+    /// Fold over the values of all pixels of the band covering the `rect`.
+    ///
+    /// `f` is called with the accumulator, the pixel and its value for every pixel of the
+    /// window returned by `rect_to_window`, skipping nodata values. The window is clamped
+    /// to the band, so a rect reaching beyond the band is fine.
+    pub fn fold_rect<B, F>(&self, band: &RasterBand, rect: &Rect<f64>, init: B, mut f: F) -> Result<B>
+        where F: FnMut(B, (usize, usize), f64) -> B {
+        let Window { offset, size } = match self.rect_to_window(rect, band.size()) {
+            Some(window) => window,
+            None => return Ok(init),
+        };
+
+        let buffer = band.read_as::<f64>((offset.0 as isize, offset.1 as isize), size, size)?;
+        let no_data = band.no_data_value();
+        let mut acc = init;
+        for (i, value) in buffer.data.into_iter().enumerate() {
+            if is_no_data(no_data, value) {
+                continue;
+            }
+            acc = f(acc, (offset.0 + i % size.0, offset.1 + i / size.0), value);
+        }
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::path::Path;
+
+    use gdal::raster::Dataset;
+    use geo_types::{Coordinate, Rect};
+
+    use crate::{GeoTransformer, PixelAnchor, PixelInterpretation};
+    use crate::sampling::{interpolate_bilinear, is_no_data};
+
+    #[test]
+    fn test_bilinear_area_vs_point() {
+        let geotransformer = GeoTransformer::try_from([0.0, 1.0, 0.0, 0.0, 0.0, -1.0]).unwrap();
+        let coordinate = Coordinate { x: 2.25, y: -3.0 };
+        // values increase by one per column
+        let ramp = |(col, _): (i64, i64)| [col as f64, col as f64 + 1.0, col as f64, col as f64 + 1.0];
+
+        let (area_origin, area_weights) = geotransformer.bilinear_neighborhood(coordinate, PixelInterpretation::Area);
+        let (point_origin, point_weights) = geotransformer.bilinear_neighborhood(coordinate, PixelInterpretation::Point);
+        assert_eq!(area_origin, (1, 2));
+        assert_eq!(point_origin, (2, 3));
+
+        let area = interpolate_bilinear(ramp(area_origin), area_weights);
+        let point = interpolate_bilinear(ramp(point_origin), point_weights);
+        assert_relative_eq!(area, 1.75, epsilon = 0.000001);
+        assert_relative_eq!(point - area, 0.5, epsilon = 0.000001);
+    }
+
+    #[test]
+    fn test_sample_band() {
+        let dataset = Dataset::open(Path::new("data/small.tiff")).unwrap();
+        let geotransformer = GeoTransformer::from_dataset(&dataset).unwrap();
+        let band = dataset.rasterband(1).unwrap();
+
+        // values of the pixels (10, 20), (11, 20), (10, 21) and (11, 21)
+        let center = geotransformer.pixel_to_coordinate_anchored((10, 20), PixelAnchor::Center);
+        assert_eq!(geotransformer.sample_nearest(&band, center).unwrap(), Some(8101.0));
+
+        // an area pixel sampled at its center returns its own value
+        let area = geotransformer.sample_bilinear(&band, center, PixelInterpretation::Area).unwrap().unwrap();
+        assert_relative_eq!(area, 8101.0, epsilon = 0.000001);
+
+        let corner = geotransformer.pixel_to_coordinate((11, 21));
+        let area = geotransformer.sample_bilinear(&band, corner, PixelInterpretation::Area).unwrap().unwrap();
+        assert_relative_eq!(area, (8101.0 + 7374.0 + 8244.0 + 8144.0) / 4.0, epsilon = 0.000001);
+        let point = geotransformer.sample_bilinear(&band, corner, PixelInterpretation::Point).unwrap().unwrap();
+        assert_relative_eq!(point, 8144.0, epsilon = 0.001);
+
+        let outside = Coordinate { x: 0.0, y: 0.0 };
+        assert_eq!(geotransformer.sample_nearest(&band, outside).unwrap(), None);
+        assert_eq!(geotransformer.sample_bilinear(&band, outside, PixelInterpretation::Area).unwrap(), None);
+        let not_finite = Coordinate { x: f64::NAN, y: f64::NAN };
+        assert_eq!(geotransformer.sample_bilinear(&band, not_finite, PixelInterpretation::Area).unwrap(), None);
+        let far_away = Coordinate { x: 1e300, y: -1e300 };
+        assert_eq!(geotransformer.sample_bilinear(&band, far_away, PixelInterpretation::Area).unwrap(), None);
+    }
+
+    #[test]
+    fn test_is_no_data() {
+        assert!(is_no_data(Some(f64::NAN), f64::NAN));
+        assert!(!is_no_data(Some(f64::NAN), 1.0));
+        assert!(is_no_data(Some(-9999.0), -9999.0));
+        assert!(!is_no_data(Some(-9999.0), f64::NAN));
+        assert!(!is_no_data(None, f64::NAN));
+    }
+
+    #[test]
+    fn test_fold_rect() {
+        let dataset = Dataset::open(Path::new("data/small.tiff")).unwrap();
+        let geotransformer = GeoTransformer::from_dataset(&dataset).unwrap();
+        let band = dataset.rasterband(1).unwrap();
+
+        // from the center of pixel (10, 20) to the center of pixel (12, 21)
+        let rect = Rect::new(
+            geotransformer.pixel_to_coordinate_anchored((10, 20), PixelAnchor::Center),
+            geotransformer.pixel_to_coordinate_anchored((12, 21), PixelAnchor::Center),
+        );
+        let (pixels, sum) = geotransformer.fold_rect(&band, &rect, (vec![], 0.0), |(mut pixels, sum), pixel, value| {
+            pixels.push(pixel);
+            (pixels, sum + value)
+        }).unwrap();
+
+        assert_eq!(pixels, vec![(10, 20), (11, 20), (12, 20), (10, 21), (11, 21), (12, 21)]);
+        assert_relative_eq!(sum, 46450.0, epsilon = 0.000001);
+    }
+}
@@ -0,0 +1,106 @@
+use geo_types::Rect;
+
+use crate::GeoTransformer;
+
+/// A rectangular window of pixels of a dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Window {
+    /// Column and row of the top-left pixel of the window
+    pub offset: (usize, usize),
+    /// Number of columns and rows of the window
+    pub size: (usize, usize),
+}
+
+impl Window {
+    /// Create a window from the pixel `offset` of its top-left corner and its `size`.
+    pub fn new(offset: (usize, usize), size: (usize, usize)) -> Window {
+        Window { offset, size }
+    }
+
+    /// Create a window from the pixel positions of its top-left and bottom-right corners.
+    ///
+    /// The corners are clamped to a dataset of the given size, `None` is returned when
+    /// the resulting window is empty.
+    pub(crate) fn from_pixel_bounds(min: (f64, f64), max: (f64, f64), size: (usize, usize)) -> Option<Window> {
+        let clamp = |value: f64, max: usize| value.max(0.0).min(max as f64) as usize;
+        let (min_col, min_row) = (clamp(min.0, size.0), clamp(min.1, size.1));
+        let (max_col, max_row) = (clamp(max.0, size.0), clamp(max.1, size.1));
+        if min_col < max_col && min_row < max_row {
+            Some(Window::new((min_col, min_row), (max_col - min_col, max_row - min_row)))
+        } else {
+            None
+        }
+    }
+
+    /// Number of pixels in the window
+    pub fn area(&self) -> usize {
+        self.size.0 * self.size.1
+    }
+
+    /// The overlapping part of both windows, or `None` when they do not overlap.
+    pub fn intersect(&self, other: &Window) -> Option<Window> {
+        let min_col = self.offset.0.max(other.offset.0);
+        let min_row = self.offset.1.max(other.offset.1);
+        let max_col = (self.offset.0 + self.size.0).min(other.offset.0 + other.size.0);
+        let max_row = (self.offset.1 + self.size.1).min(other.offset.1 + other.size.1);
+        if min_col < max_col && min_row < max_row {
+            Some(Window::new((min_col, min_row), (max_col - min_col, max_row - min_row)))
+        } else {
+            None
+        }
+    }
+
+    /// The part of the window within a dataset of the given size, or `None` when the
+    /// window is completely outside of it.
+    pub fn clamp(&self, size: (usize, usize)) -> Option<Window> {
+        self.intersect(&Window::new((0, 0), size))
+    }
+
+    /// Check if the pixel of the dataset is within the window.
+    pub fn contains_pixel(&self, pixel: (usize, usize)) -> bool {
+        pixel.0 >= self.offset.0 && pixel.0 < self.offset.0 + self.size.0
+            && pixel.1 >= self.offset.1 && pixel.1 < self.offset.1 + self.size.1
+    }
+
+    /// The envelope of the geo-coordinates of the window.
+    pub fn to_rect(&self, geotransformer: &GeoTransformer) -> Rect<f64> {
+        geotransformer.window_to_rect(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::{GeoTransformer, Window};
+
+    #[test]
+    fn test_window_intersect() {
+        let window = Window::new((2, 3), (10, 5));
+
+        assert_eq!(window.intersect(&Window::new((8, 0), (10, 5))), Some(Window::new((8, 3), (4, 2))));
+        assert_eq!(window.intersect(&Window::new((12, 3), (10, 5))), None);
+        assert_eq!(window.clamp((5, 100)), Some(Window::new((2, 3), (3, 5))));
+        assert_eq!(window.area(), 50);
+    }
+
+    #[test]
+    fn test_window_contains_pixel() {
+        let window = Window::new((2, 3), (10, 5));
+
+        assert!(window.contains_pixel((2, 3)));
+        assert!(window.contains_pixel((11, 7)));
+        assert!(!window.contains_pixel((12, 7)));
+        assert!(!window.contains_pixel((1, 3)));
+    }
+
+    #[test]
+    fn test_window_to_rect() {
+        let geotransformer = GeoTransformer::try_from([10.0, 0.5, 0.0, 50.0, 0.0, -0.5]).unwrap()
+            .rotated_about(0.3, (0.0, 0.0))
+            .unwrap();
+        let window = Window::new((2, 3), (10, 5));
+
+        assert_eq!(window.to_rect(&geotransformer), geotransformer.window_to_rect(&window));
+    }
+}